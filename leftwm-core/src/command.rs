@@ -1,5 +1,12 @@
-use crate::{layouts::Layout, models::TagId};
+use crate::{
+    layouts::Layout,
+    models::{SnapPosition, SplitDirection, TagId},
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::str::FromStr;
+use thiserror::Error;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub enum Command {
@@ -11,6 +18,9 @@ pub enum Command {
     ToggleScratchPad(String),
     ToggleFullScreen,
     ToggleSticky,
+    ToggleAbove,
+    ToggleBelow,
+    ToggleMaximize,
     GotoTag(TagId),
     FloatingToTile,
     TileToFloating,
@@ -37,5 +47,484 @@ pub enum Command {
     DecreaseMainWidth(i8),
     SetMarginMultiplier(f32),
     SendWorkspaceToTag(usize, usize),
+    MoveFloating(i32, i32),
+    ResizeFloating(i32, i32),
+    CenterFloating,
+    SnapFloating(SnapPosition),
+    ToggleStruts,
+    ToggleGutters,
+    ToggleDocks,
+    ZenMode,
+    SplitWorkspace(SplitDirection, u8),
+    MergeWorkspace,
+    ResizeWorkspaceSplit(i8),
+    /// Run the commands in order, stopping at the first one that cannot be applied.
+    Sequence(Vec<Command>),
+    /// Run the commands of an alias defined in the config.
+    Alias(String),
     Other(String),
 }
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ParseCommandError {
+    #[error("Empty command")]
+    Empty,
+    #[error("Unterminated quote in: {0}")]
+    UnterminatedQuote(String),
+    #[error("{command} is missing its {argument}")]
+    MissingArgument {
+        command: String,
+        argument: &'static str,
+    },
+    #[error("{command} has an invalid {argument}: `{value}`")]
+    InvalidArgument {
+        command: String,
+        argument: &'static str,
+        value: String,
+    },
+    #[error("{command} has an unexpected argument: `{value}`")]
+    UnexpectedArgument { command: String, value: String },
+    #[error("Unknown command `{0}`")]
+    Unknown(String),
+}
+
+/// Commands that are not built in but handled by the config, kept as [`Command::Other`].
+pub const CONFIG_COMMANDS: &[&str] = &["LoadTheme", "UnloadTheme"];
+
+/// Why a command could not be applied to the current state.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    #[error("No window is focused")]
+    NoFocusedWindow,
+    #[error("The focused window is not floating")]
+    NotFloating,
+    #[error("Tag {0} does not exist")]
+    NoSuchTag(TagId),
+    #[error("Workspace {0} does not exist")]
+    NoSuchWorkspace(usize),
+    #[error("Scratchpad `{0}` does not exist")]
+    NoSuchScratchPad(String),
+    #[error("Alias `{0}` does not exist")]
+    NoSuchAlias(String),
+    #[error("Alias `{0}` uses itself")]
+    RecursiveAlias(String),
+    #[error("Unknown command `{0}`")]
+    Unknown(String),
+    #[error("Cannot load the theme {0}")]
+    CannotLoadTheme(String),
+}
+
+/// The commands the alias `name` stands for, with the aliases it uses replaced by their commands.
+/// # Errors
+///
+/// Will error if an alias is not defined, or uses itself.
+pub fn resolve_alias<S: BuildHasher>(
+    aliases: &HashMap<String, Vec<Command>, S>,
+    name: &str,
+) -> Result<Vec<Command>, CommandError> {
+    resolve_alias_within(aliases, name, &mut vec![])
+}
+
+fn resolve_alias_within<'a, S: BuildHasher>(
+    aliases: &'a HashMap<String, Vec<Command>, S>,
+    name: &'a str,
+    resolving: &mut Vec<&'a str>,
+) -> Result<Vec<Command>, CommandError> {
+    if resolving.contains(&name) {
+        return Err(CommandError::RecursiveAlias(name.to_string()));
+    }
+    let commands = aliases
+        .get(name)
+        .ok_or_else(|| CommandError::NoSuchAlias(name.to_string()))?;
+    resolving.push(name);
+    let mut resolved = vec![];
+    flatten_within(aliases, commands, resolving, &mut resolved)?;
+    resolving.pop();
+    Ok(resolved)
+}
+
+/// Appends `commands` to `resolved`, replacing sequences and aliases by their commands.
+fn flatten_within<'a, S: BuildHasher>(
+    aliases: &'a HashMap<String, Vec<Command>, S>,
+    commands: &'a [Command],
+    resolving: &mut Vec<&'a str>,
+    resolved: &mut Vec<Command>,
+) -> Result<(), CommandError> {
+    for command in commands {
+        match command {
+            Command::Alias(alias) => {
+                resolved.extend(resolve_alias_within(aliases, alias, resolving)?);
+            }
+            Command::Sequence(commands) => flatten_within(aliases, commands, resolving, resolved)?,
+            _ => resolved.push(command.clone()),
+        }
+    }
+    Ok(())
+}
+
+/// Parses commands written as their name followed by their arguments, like `MoveFloating 10 -20`.
+///
+/// Arguments are separated by whitespace, and can be quoted with `"` or `'` to contain spaces.
+/// `Execute` takes the rest of the line as is, `ToggleScratchPad` takes the rest of the line as
+/// the name unless it is quoted, `Sequence` takes each argument as a command, and the
+/// [`CONFIG_COMMANDS`] are kept as `Other` to be handled by the config. Other names are errors.
+impl FromStr for Command {
+    type Err = ParseCommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let line = s.trim();
+        let (name, rest) = line
+            .split_once(char::is_whitespace)
+            .map_or((line, ""), |(name, rest)| (name, rest.trim()));
+        parse_command(name, rest, line, CONFIG_COMMANDS)
+    }
+}
+
+impl Command {
+    /// Parses the command `name` with its `arguments`, written like they follow the name on a
+    /// command line. For configs that give them separately, like the keybinds of `config.toml`.
+    /// # Errors
+    ///
+    /// Will error if the arguments don't match the ones of the command.
+    pub fn from_parts(name: &str, arguments: &str) -> Result<Self, ParseCommandError> {
+        let arguments = arguments.trim();
+        if arguments.is_empty() {
+            return parse_command(name, arguments, name, CONFIG_COMMANDS);
+        }
+        let line = name.to_string() + " " + arguments;
+        parse_command(name, arguments, &line, CONFIG_COMMANDS)
+    }
+}
+
+/// Parses the command `name` followed by `rest`. `line` is kept for the commands of the config,
+/// which are the ones named in `config_commands`.
+fn parse_command(
+    name: &str,
+    rest: &str,
+    line: &str,
+    config_commands: &[&str],
+) -> Result<Command, ParseCommandError> {
+    let mut args = Arguments {
+        command: name,
+        rest,
+        values: None,
+    };
+    let command = match name {
+        "" => return Err(ParseCommandError::Empty),
+        "Execute" if rest.is_empty() => return Err(args.missing("shell command")),
+        "Execute" => return Ok(Command::Execute(rest.to_string())),
+        "CloseWindow" => Command::CloseWindow,
+        "SwapScreens" | "SwapTags" => Command::SwapScreens,
+        "SoftReload" => Command::SoftReload,
+        "HardReload" => Command::HardReload,
+        "ToggleScratchPad" => Command::ToggleScratchPad(args.rest("scratchpad name")?),
+        "ToggleFullScreen" => Command::ToggleFullScreen,
+        "ToggleSticky" => Command::ToggleSticky,
+        "ToggleAbove" => Command::ToggleAbove,
+        "ToggleBelow" => Command::ToggleBelow,
+        "ToggleMaximize" => Command::ToggleMaximize,
+        "GotoTag" => Command::GotoTag(args.next("tag index")?),
+        "FloatingToTile" => Command::FloatingToTile,
+        "TileToFloating" => Command::TileToFloating,
+        "ToggleFloating" => Command::ToggleFloating,
+        "MoveWindowUp" => Command::MoveWindowUp,
+        "MoveWindowDown" => Command::MoveWindowDown,
+        "MoveWindowTop" => Command::MoveWindowTop,
+        "FocusNextTag" => Command::FocusNextTag,
+        "FocusPreviousTag" => Command::FocusPreviousTag,
+        "FocusWindowUp" => Command::FocusWindowUp,
+        "FocusWindowDown" => Command::FocusWindowDown,
+        "FocusWorkspaceNext" => Command::FocusWorkspaceNext,
+        "FocusWorkspacePrevious" => Command::FocusWorkspacePrevious,
+        "SendWindowToTag" | "MoveToTag" => Command::SendWindowToTag(args.next("tag index")?),
+        "MoveWindowToLastWorkspace" | "MoveToLastWorkspace" => Command::MoveWindowToLastWorkspace,
+        "MoveWindowToNextWorkspace" => Command::MoveWindowToNextWorkspace,
+        "MoveWindowToPreviousWorkspace" => Command::MoveWindowToPreviousWorkspace,
+        "MouseMoveWindow" => Command::MouseMoveWindow,
+        "NextLayout" => Command::NextLayout,
+        "PreviousLayout" => Command::PreviousLayout,
+        "SetLayout" => Command::SetLayout(args.next("layout")?),
+        "RotateTag" => Command::RotateTag,
+        "IncreaseMainWidth" => Command::IncreaseMainWidth(args.next("delta")?),
+        "DecreaseMainWidth" => Command::DecreaseMainWidth(args.next("delta")?),
+        "SetMarginMultiplier" => Command::SetMarginMultiplier(args.next("multiplier")?),
+        "SendWorkspaceToTag" => {
+            Command::SendWorkspaceToTag(args.next("workspace index")?, args.next("tag index")?)
+        }
+        "MoveFloating" => Command::MoveFloating(args.next("dx")?, args.next("dy")?),
+        "ResizeFloating" => Command::ResizeFloating(args.next("dw")?, args.next("dh")?),
+        "CenterFloating" => Command::CenterFloating,
+        "SnapFloating" => Command::SnapFloating(args.next("snap position")?),
+        "ToggleStruts" => Command::ToggleStruts,
+        "ToggleGutters" => Command::ToggleGutters,
+        "ToggleDocks" => Command::ToggleDocks,
+        "ZenMode" => Command::ZenMode,
+        "SplitWorkspace" => {
+            Command::SplitWorkspace(args.next("split direction")?, args.next("ratio")?)
+        }
+        "MergeWorkspace" => Command::MergeWorkspace,
+        "ResizeWorkspaceSplit" => Command::ResizeWorkspaceSplit(args.next("delta")?),
+        "Sequence" => Command::Sequence(args.commands()?),
+        "Alias" => Command::Alias(args.next("alias name")?),
+        _ if config_commands.contains(&name) => return Ok(Command::Other(line.to_string())),
+        _ => return Err(ParseCommandError::Unknown(name.to_string())),
+    };
+    args.finish()?;
+    Ok(command)
+}
+
+/// The arguments following the name of a command.
+///
+/// They are only split when a command asks for them, so commands taking the line as is, like
+/// `Execute`, are not rejected for quotes that are not meant for us.
+struct Arguments<'a> {
+    command: &'a str,
+    rest: &'a str,
+    values: Option<std::vec::IntoIter<String>>,
+}
+
+impl Arguments<'_> {
+    fn values(&mut self) -> Result<&mut std::vec::IntoIter<String>, ParseCommandError> {
+        let values = match self.values.take() {
+            Some(values) => values,
+            None => split_arguments(self.rest)?.into_iter(),
+        };
+        Ok(self.values.insert(values))
+    }
+
+    fn next<T: FromStr>(&mut self, argument: &'static str) -> Result<T, ParseCommandError> {
+        let value = self
+            .values()?
+            .next()
+            .ok_or_else(|| self.missing(argument))?;
+        value
+            .parse()
+            .map_err(|_| ParseCommandError::InvalidArgument {
+                command: self.command.to_string(),
+                argument,
+                value,
+            })
+    }
+
+    /// Takes the arguments as they are written, for names that may contain spaces without being
+    /// quoted. A quoted name is taken as a single argument.
+    fn rest(&mut self, argument: &'static str) -> Result<String, ParseCommandError> {
+        if self.rest.starts_with(&['"', '\''][..]) {
+            return self.next(argument);
+        }
+        if self.rest.is_empty() {
+            return Err(self.missing(argument));
+        }
+        self.values = Some(Vec::new().into_iter());
+        Ok(self.rest.to_string())
+    }
+
+    /// Parses all remaining arguments as commands.
+    fn commands(&mut self) -> Result<Vec<Command>, ParseCommandError> {
+        let commands = self
+            .values()?
+            .map(|command| command.parse())
+            .collect::<Result<Vec<Command>, _>>()?;
+        if commands.is_empty() {
+            return Err(self.missing("commands"));
+        }
+        Ok(commands)
+    }
+
+    fn missing(&self, argument: &'static str) -> ParseCommandError {
+        ParseCommandError::MissingArgument {
+            command: self.command.to_string(),
+            argument,
+        }
+    }
+
+    fn finish(mut self) -> Result<(), ParseCommandError> {
+        match self.values()?.next() {
+            Some(value) => Err(ParseCommandError::UnexpectedArgument {
+                command: self.command.to_string(),
+                value,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Splits arguments on whitespace, keeping quoted arguments together.
+fn split_arguments(s: &str) -> Result<Vec<String>, ParseCommandError> {
+    let mut arguments = vec![];
+    let mut current: Option<String> = None;
+    let mut quote = None;
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), '\\') => {
+                let escaped = chars.next().unwrap_or('\\');
+                current.get_or_insert_with(String::new).push(escaped);
+            }
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => arguments.extend(current.take()),
+            (_, c) => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err(ParseCommandError::UnterminatedQuote(s.to_string()));
+    }
+    arguments.extend(current);
+    Ok(arguments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_should_parse_typed_arguments() {
+        assert_eq!("CloseWindow".parse(), Ok(Command::CloseWindow));
+        assert_eq!(
+            "IncreaseMainWidth 5".parse(),
+            Ok(Command::IncreaseMainWidth(5))
+        );
+        assert_eq!(
+            "  MoveFloating 10   -20 ".parse(),
+            Ok(Command::MoveFloating(10, -20))
+        );
+        assert_eq!(
+            "SetLayout Monocle".parse(),
+            Ok(Command::SetLayout(Layout::Monocle))
+        );
+        assert_eq!("MoveToTag 3".parse(), Ok(Command::SendWindowToTag(3)));
+    }
+
+    #[test]
+    fn quoted_arguments_can_contain_spaces() {
+        assert_eq!(
+            r#"ToggleScratchPad "My \"notes\"""#.parse(),
+            Ok(Command::ToggleScratchPad("My \"notes\"".to_string()))
+        );
+        assert_eq!(
+            "Execute notify-send 'a b'".parse(),
+            Ok(Command::Execute("notify-send 'a b'".to_string()))
+        );
+        assert_eq!(
+            "ToggleScratchPad Music player".parse(),
+            Ok(Command::ToggleScratchPad("Music player".to_string()))
+        );
+        assert_eq!(
+            "ToggleScratchPad Music  player".parse(),
+            Ok(Command::ToggleScratchPad("Music  player".to_string()))
+        );
+        assert_eq!(
+            Command::from_parts("ToggleScratchPad", " Music  player"),
+            Ok(Command::ToggleScratchPad("Music  player".to_string()))
+        );
+        assert_eq!(
+            Command::from_parts("MoveFloating", "10 -20"),
+            Ok(Command::MoveFloating(10, -20))
+        );
+        assert_eq!(
+            "ToggleScratchPad 'open".parse::<Command>(),
+            Err(ParseCommandError::UnterminatedQuote("'open".to_string()))
+        );
+    }
+
+    #[test]
+    fn errors_should_name_the_argument() {
+        assert_eq!(
+            "SendWorkspaceToTag 1".parse::<Command>(),
+            Err(ParseCommandError::MissingArgument {
+                command: "SendWorkspaceToTag".to_string(),
+                argument: "tag index",
+            })
+        );
+        assert_eq!(
+            "GotoTag one".parse::<Command>(),
+            Err(ParseCommandError::InvalidArgument {
+                command: "GotoTag".to_string(),
+                argument: "tag index",
+                value: "one".to_string(),
+            })
+        );
+        assert_eq!(
+            "CloseWindow now".parse::<Command>(),
+            Err(ParseCommandError::UnexpectedArgument {
+                command: "CloseWindow".to_string(),
+                value: "now".to_string(),
+            })
+        );
+        assert_eq!(
+            "LoadTheme /some/theme.toml".parse(),
+            Ok(Command::Other("LoadTheme /some/theme.toml".to_string()))
+        );
+        assert_eq!(
+            "GotoTga 2".parse::<Command>(),
+            Err(ParseCommandError::Unknown("GotoTga".to_string()))
+        );
+        assert_eq!(
+            "Sequence 'GotoTag 2' Frobnicate".parse::<Command>(),
+            Err(ParseCommandError::Unknown("Frobnicate".to_string()))
+        );
+    }
+
+    #[test]
+    fn commands_taking_the_line_as_is_should_allow_any_quote() {
+        assert_eq!(
+            "Execute notify-send it's done".parse(),
+            Ok(Command::Execute("notify-send it's done".to_string()))
+        );
+        assert_eq!(
+            "LoadTheme /tmp/it's".parse(),
+            Ok(Command::Other("LoadTheme /tmp/it's".to_string()))
+        );
+    }
+
+    #[test]
+    fn sequences_should_hold_a_command_per_argument() {
+        assert_eq!(
+            "Sequence 'SendWindowToTag 3' \"GotoTag 3\" \"Execute notify-send 'moved'\"".parse(),
+            Ok(Command::Sequence(vec![
+                Command::SendWindowToTag(3),
+                Command::GotoTag(3),
+                Command::Execute("notify-send 'moved'".to_string()),
+            ]))
+        );
+        assert_eq!(
+            "Sequence 'GotoTag x'".parse::<Command>(),
+            Err(ParseCommandError::InvalidArgument {
+                command: "GotoTag".to_string(),
+                argument: "tag index",
+                value: "x".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn aliases_should_be_resolved_through_other_aliases() {
+        let mut aliases = HashMap::new();
+        aliases.insert(
+            "follow3".to_string(),
+            vec![
+                Command::SendWindowToTag(3),
+                Command::Alias("goto3".to_string()),
+            ],
+        );
+        aliases.insert("goto3".to_string(), vec![Command::GotoTag(3)]);
+        let nested_loop = Command::Sequence(vec![Command::Alias("loop".to_string())]);
+        aliases.insert("loop".to_string(), vec![nested_loop]);
+        assert_eq!(
+            resolve_alias(&aliases, "follow3"),
+            Ok(vec![Command::SendWindowToTag(3), Command::GotoTag(3)])
+        );
+        assert_eq!(
+            resolve_alias(&aliases, "loop"),
+            Err(CommandError::RecursiveAlias("loop".to_string()))
+        );
+        assert_eq!(
+            resolve_alias(&aliases, "missing"),
+            Err(CommandError::NoSuchAlias("missing".to_string()))
+        );
+    }
+}
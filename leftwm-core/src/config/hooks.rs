@@ -0,0 +1,52 @@
+use crate::Event;
+use serde::{Deserialize, Serialize};
+
+/// Shell commands run when something happens in the manager, like `on_focus_change`.
+///
+/// The event is described to the command by environment variables, see
+/// [`crate::utils::hooks`].
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Hooks {
+    pub on_new_window: Option<String>,
+    pub on_window_close: Option<String>,
+    pub on_focus_change: Option<String>,
+    pub on_tag_change: Option<String>,
+    pub on_layout_change: Option<String>,
+    pub on_workspace_change: Option<String>,
+    /// Run when a window starts demanding attention.
+    pub on_urgent: Option<String>,
+}
+
+impl Hooks {
+    /// These hooks, replaced by the ones set in `overrides`.
+    #[must_use]
+    pub fn merge(self, overrides: Self) -> Self {
+        Self {
+            on_new_window: overrides.on_new_window.or(self.on_new_window),
+            on_window_close: overrides.on_window_close.or(self.on_window_close),
+            on_focus_change: overrides.on_focus_change.or(self.on_focus_change),
+            on_tag_change: overrides.on_tag_change.or(self.on_tag_change),
+            on_layout_change: overrides.on_layout_change.or(self.on_layout_change),
+            on_workspace_change: overrides.on_workspace_change.or(self.on_workspace_change),
+            on_urgent: overrides.on_urgent.or(self.on_urgent),
+        }
+    }
+
+    /// The name and command of the hook run for `event`, if it is set.
+    pub fn for_event(&self, event: &Event) -> Option<(&'static str, &str)> {
+        let (name, command) = match event {
+            Event::WindowCreated { .. } => ("on_new_window", &self.on_new_window),
+            Event::WindowClosed { .. } => ("on_window_close", &self.on_window_close),
+            Event::FocusChanged { .. } => ("on_focus_change", &self.on_focus_change),
+            Event::TagChanged { .. } => ("on_tag_change", &self.on_tag_change),
+            Event::LayoutChanged { .. } => ("on_layout_change", &self.on_layout_change),
+            Event::WorkspaceChanged { .. } => ("on_workspace_change", &self.on_workspace_change),
+            Event::UrgencyChanged { urgent: true, .. } => ("on_urgent", &self.on_urgent),
+            Event::UrgencyChanged { .. }
+            | Event::TitleChanged { .. }
+            | Event::ModeChanged { .. } => return None,
+        };
+        Some((name, command.as_deref()?))
+    }
+}
@@ -1,14 +1,21 @@
+mod hooks;
 mod keybind;
 mod scratchpad;
 mod workspace_config;
 
 use crate::display_servers::DisplayServer;
 use crate::layouts::Layout;
-pub use crate::models::{FocusBehaviour, Gutter, Margins, Size};
+pub use crate::models::{
+    FloatingPlacement, FocusBehaviour, FocusStealingPolicy, Gutter, Margins, Size, TagMode,
+};
 use crate::models::{LayoutMode, Manager};
 use crate::state::State;
+use crate::{Command, CommandError};
+pub use hooks::Hooks;
 pub use keybind::Keybind;
 pub use scratchpad::ScratchPad;
+use std::collections::HashMap;
+use std::path::PathBuf;
 pub use workspace_config::Workspace;
 
 pub trait Config {
@@ -32,13 +39,40 @@ pub trait Config {
 
     fn layout_mode(&self) -> LayoutMode;
 
+    /// Whether the workspaces share the tags, or each has its own.
+    fn tag_mode(&self) -> TagMode;
+
     fn focus_new_windows(&self) -> bool;
 
+    /// Placement of new floating windows, `None` keeps the position requested by the window.
+    fn floating_placement(&self) -> Option<FloatingPlacement>;
+
+    /// What happens when a window asks for the focus.
+    fn focus_stealing_policy(&self) -> FocusStealingPolicy;
+
+    /// Focus stealing policies of specific window classes.
+    fn focus_stealing_overrides(&self) -> HashMap<String, FocusStealingPolicy>;
+
     fn command_handler<SERVER>(command: &str, manager: &mut Manager<Self, SERVER>) -> bool
     where
         SERVER: DisplayServer,
         Self: Sized;
 
+    /// Runs a command of the config like [`Self::command_handler`], telling why it failed.
+    /// # Errors
+    ///
+    /// Will error if the config does not know the command, or cannot apply it.
+    fn checked_command_handler<SERVER>(
+        command: &str,
+        manager: &mut Manager<Self, SERVER>,
+    ) -> Result<bool, CommandError>
+    where
+        SERVER: DisplayServer,
+        Self: Sized,
+    {
+        Ok(Self::command_handler(command, manager))
+    }
+
     fn always_float(&self) -> bool;
     fn default_width(&self) -> i32;
     fn default_height(&self) -> i32;
@@ -49,10 +83,17 @@ pub trait Config {
     fn default_border_color(&self) -> String;
     fn floating_border_color(&self) -> String;
     fn focused_border_color(&self) -> String;
-    fn on_new_window_cmd(&self) -> Option<String>;
+    /// Commands run when something happens, like a change of focus.
+    fn hooks(&self) -> Hooks;
+
+    /// Named lists of commands, run with `Alias`.
+    fn aliases(&self) -> &HashMap<String, Vec<Command>>;
     fn get_list_of_gutters(&self) -> Vec<Gutter>;
     fn max_window_width(&self) -> Option<Size>;
 
+    /// Whether tiled windows are shrunk to honour their ICCCM size hints.
+    fn honour_size_hints(&self) -> bool;
+
     /// Attempt to write current state to a file.
     ///
     /// It will be used to restore the state after soft reload.
@@ -62,24 +103,38 @@ pub trait Config {
 
     /// Load saved state if it exists.
     fn load_state(&self, state: &mut State);
+
+    /// Files the config was read from, watched to reload it when they change.
+    fn watched_files(&self) -> Vec<PathBuf>;
+
+    /// Re-read the config from its files.
+    ///
+    /// If they cannot be read, the current config is kept and `false` is returned.
+    fn reload_from_files(&mut self) -> bool;
 }
 
 #[cfg(test)]
 #[allow(clippy::module_name_repetitions)]
+#[derive(Clone)]
 pub struct TestConfig {
     pub tags: Vec<String>,
+    pub keybinds: Vec<Keybind>,
+    pub floating_placement: Option<FloatingPlacement>,
+    pub workspaces: Vec<Workspace>,
+    pub tag_mode: TagMode,
+    pub aliases: HashMap<String, Vec<Command>>,
 }
 
 #[cfg(test)]
 impl Config for TestConfig {
     fn mapped_bindings(&self) -> Vec<Keybind> {
-        unimplemented!()
+        self.keybinds.clone()
     }
     fn create_list_of_tag_labels(&self) -> Vec<String> {
         self.tags.clone()
     }
     fn workspaces(&self) -> Option<Vec<Workspace>> {
-        unimplemented!()
+        Some(self.workspaces.clone())
     }
     fn focus_behaviour(&self) -> FocusBehaviour {
         FocusBehaviour::Sloppy
@@ -99,9 +154,21 @@ impl Config for TestConfig {
     fn layout_mode(&self) -> LayoutMode {
         LayoutMode::Workspace
     }
+    fn tag_mode(&self) -> TagMode {
+        self.tag_mode
+    }
     fn focus_new_windows(&self) -> bool {
         false
     }
+    fn floating_placement(&self) -> Option<FloatingPlacement> {
+        self.floating_placement
+    }
+    fn focus_stealing_policy(&self) -> FocusStealingPolicy {
+        FocusStealingPolicy::Focus
+    }
+    fn focus_stealing_overrides(&self) -> HashMap<String, FocusStealingPolicy> {
+        HashMap::new()
+    }
     fn command_handler<SERVER>(command: &str, manager: &mut Manager<Self, SERVER>) -> bool
     where
         SERVER: DisplayServer,
@@ -141,8 +208,11 @@ impl Config for TestConfig {
     fn focused_border_color(&self) -> String {
         unimplemented!()
     }
-    fn on_new_window_cmd(&self) -> Option<String> {
-        None
+    fn hooks(&self) -> Hooks {
+        Hooks::default()
+    }
+    fn aliases(&self) -> &HashMap<String, Vec<Command>> {
+        &self.aliases
     }
     fn get_list_of_gutters(&self) -> Vec<Gutter> {
         Default::default()
@@ -150,12 +220,21 @@ impl Config for TestConfig {
     fn max_window_width(&self) -> Option<Size> {
         None
     }
+    fn honour_size_hints(&self) -> bool {
+        false
+    }
     fn save_state(&self, _state: &State) {
         unimplemented!()
     }
     fn load_state(&self, _state: &mut State) {
         unimplemented!()
     }
+    fn watched_files(&self) -> Vec<PathBuf> {
+        vec![]
+    }
+    fn reload_from_files(&mut self) -> bool {
+        false
+    }
 }
 
 #[cfg(test)]
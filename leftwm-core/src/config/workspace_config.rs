@@ -1,13 +1,36 @@
 use serde::{Deserialize, Serialize};
 
-use crate::models::Size;
+use crate::layouts::Layout;
+use crate::models::{Gutter, Margins, Size};
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct Workspace {
+    #[serde(default)]
     pub x: i32,
+    #[serde(default)]
     pub y: i32,
+    #[serde(default)]
     pub height: i32,
+    #[serde(default)]
     pub width: i32,
     pub id: Option<i32>,
     pub max_window_width: Option<Size>,
+    /// Name of the `RandR` output (like `DP-2`) the workspace follows, instead of a fixed geometry.
+    pub output: Option<String>,
+    pub layout: Option<Layout>,
+    /// Label of the tag shown when the workspace is created.
+    pub default_tag: Option<String>,
+    /// Replaces the `workspace_margin` of the theme on this output.
+    pub margin: Option<Margins>,
+    /// Replaces the gutters of the theme on this output, for the sides they are set on.
+    pub gutters: Option<Vec<Gutter>>,
+}
+
+impl Workspace {
+    /// Whether the workspaces follow the monitors that are connected, rather than being placed
+    /// at fixed positions.
+    #[must_use]
+    pub fn follow_monitors(workspaces: &[Self]) -> bool {
+        workspaces.is_empty() || workspaces.iter().any(|ws| ws.output.is_some())
+    }
 }
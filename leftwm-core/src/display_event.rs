@@ -1,9 +1,9 @@
 use super::{models::Screen, models::Window, models::WindowHandle, Button, ModMask, XKeysym};
-use crate::models::WindowChange;
+use crate::models::{TagId, WindowChange};
 use crate::Command;
 
 #[allow(clippy::large_enum_variant)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum DisplayEvent {
     Movement(WindowHandle, i32, i32),
     KeyCombo(ModMask, XKeysym),
@@ -12,12 +12,22 @@ pub enum DisplayEvent {
     WindowCreate(Window, i32, i32),
     WindowChange(WindowChange),
     WindowDestroy(WindowHandle),
+    WindowClose(WindowHandle), // A client asked to close the window.
     MouseEnteredWindow(WindowHandle),
     VerifyFocusedAt(WindowHandle), // Request focus validation for this window.
     MoveFocusTo(i32, i32),         // Focus the nearest window to this point.
     MoveWindow(WindowHandle, i32, i32),
     ResizeWindow(WindowHandle, i32, i32),
+    StartMovingWindow(WindowHandle), // A client asked to be moved with the mouse.
+    StartResizingWindow(WindowHandle), // A client asked to be resized with the mouse.
+    // A window asked to be focused, with the user time of the request when it didn't come from a
+    // pager.
+    WindowActivate(WindowHandle, Option<u64>),
+    WindowSendToTag(WindowHandle, TagId),
+    RaiseWindow(WindowHandle),
+    LowerWindow(WindowHandle),
     ScreenCreate(Screen),
+    ScreensChanged(Vec<Screen>), // The monitors were plugged, unplugged or reconfigured.
     SendCommand(Command),
     ChangeToNormalMode,
 }
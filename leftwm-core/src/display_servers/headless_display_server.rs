@@ -0,0 +1,41 @@
+use super::Config;
+use super::DisplayEvent;
+use super::DisplayServer;
+use crate::display_action::DisplayAction;
+use futures::prelude::*;
+use std::pin::Pin;
+
+/// A display server without a display, for running a [`crate::Manager`] without X.
+///
+/// It never produces events and ignores the actions it is asked to perform, the manager is
+/// driven by calling its handlers directly.
+#[derive(Debug, Clone, Default)]
+pub struct HeadlessDisplayServer {
+    /// Number of actions requested by the manager so far.
+    pub actions: usize,
+}
+
+impl DisplayServer for HeadlessDisplayServer {
+    fn new(_: &impl Config) -> Self {
+        Self::default()
+    }
+
+    fn get_next_events(&mut self) -> Vec<DisplayEvent> {
+        vec![]
+    }
+
+    fn execute_action(&mut self, _act: DisplayAction) -> Option<DisplayEvent> {
+        self.actions += 1;
+        None
+    }
+
+    fn wait_readable(&self) -> Pin<Box<dyn Future<Output = ()>>> {
+        Box::pin(future::pending())
+    }
+
+    fn flush(&self) {}
+
+    fn generate_verify_focus_event(&self) -> Option<DisplayEvent> {
+        None
+    }
+}
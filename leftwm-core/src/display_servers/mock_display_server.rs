@@ -6,16 +6,21 @@ use crate::models::Screen;
 #[derive(Clone)]
 pub struct MockDisplayServer {
     pub screens: Vec<Screen>,
+    /// Events returned by the next call to `get_next_events`.
+    pub events: Vec<DisplayEvent>,
 }
 
 impl DisplayServer for MockDisplayServer {
     fn new(_: &impl Config) -> Self {
-        Self { screens: vec![] }
+        Self {
+            screens: vec![],
+            events: vec![],
+        }
     }
 
     //testing a couple mock event
     fn get_next_events(&mut self) -> Vec<DisplayEvent> {
-        vec![]
+        std::mem::take(&mut self.events)
     }
 
     fn wait_readable(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()>>> {
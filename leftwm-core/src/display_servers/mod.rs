@@ -4,12 +4,14 @@ use crate::models::Window;
 use crate::models::Workspace;
 use crate::state::State;
 use crate::DisplayEvent;
+mod headless_display_server;
 #[cfg(test)]
 mod mock_display_server;
 pub mod xlib_display_server;
 use futures::prelude::*;
 use std::pin::Pin;
 
+pub use self::headless_display_server::HeadlessDisplayServer;
 #[cfg(test)]
 pub use self::mock_display_server::MockDisplayServer;
 pub use self::xlib_display_server::XlibDisplayServer;
@@ -12,16 +12,26 @@ use crate::models::Xyhw;
 use crate::models::XyhwChange;
 use models::FocusBehaviour;
 use std::os::raw::c_ulong;
-use x11_dl::xlib;
+use x11_dl::{xlib, xrandr};
 
 pub struct XEvent<'a>(pub &'a mut XWrap, pub xlib::XEvent);
 
 impl<'a> From<XEvent<'a>> for Option<DisplayEvent> {
     fn from(x_event: XEvent) -> Self {
         let xw = x_event.0;
-        let raw_event = x_event.1;
+        let mut raw_event = x_event.1;
 
         match raw_event.get_type() {
+            // a monitor was plugged, unplugged or reconfigured
+            t if Some(t)
+                == xw
+                    .randr_event_base
+                    .map(|b| b + xrandr::RRScreenChangeNotify) =>
+            {
+                xw.update_screen_configuration(&mut raw_event);
+                Some(DisplayEvent::ScreensChanged(xw.get_screens()))
+            }
+
             // new window is created
             xlib::MapRequest => from_map_request(raw_event, xw),
 
@@ -100,6 +110,8 @@ fn from_map_request(raw_event: xlib::XEvent, xw: &mut XWrap) -> Option<DisplayEv
 
     // Build the new window, and fill in info about it.
     let mut w = Window::new(handle, name, pid);
+    w.res_class = xw.get_window_class(event.window);
+    w.user_time = xw.get_window_user_time(event.window);
     w.r#type = r#type;
     w.set_states(states);
     if let Some(trans) = trans {
@@ -127,6 +139,7 @@ fn from_map_request(raw_event: xlib::XEvent, xw: &mut XWrap) -> Option<DisplayEv
         hint.update(&mut requested);
     }
     w.requested = Some(requested);
+    w.size_hints = xw.get_size_hints(event.window);
     w.can_resize = can_resize;
     if let Some(hint) = wm_hint {
         w.never_focus = hint.flags & xlib::InputHint != 0 && hint.input == 0;
@@ -2,9 +2,9 @@ use super::DisplayEvent;
 use super::XWrap;
 use crate::models::WindowChange;
 use crate::models::WindowHandle;
+use crate::models::XyhwChange;
 use crate::Command;
 use std::convert::TryFrom;
-use std::os::raw::c_long;
 use x11_dl::xlib;
 
 pub fn from_event(xw: &XWrap, event: xlib::XClientMessageEvent) -> Option<DisplayEvent> {
@@ -28,40 +28,116 @@ pub fn from_event(xw: &XWrap, event: xlib::XClientMessageEvent) -> Option<Displa
         }
     }
 
-    //if the client is trying to toggle fullscreen without changing the window state, change it too
-    if event.message_type == xw.atoms.NetWMState
-        && (event.data.get_long(1) == xw.atoms.NetWMStateFullscreen as c_long
-            || event.data.get_long(2) == xw.atoms.NetWMStateFullscreen as c_long)
-    {
-        let set_fullscreen = event.data.get_long(0) == 1;
-        let toggle_fullscreen = event.data.get_long(0) == 2;
-        let mut states = xw.get_window_states_atoms(event.window);
+    let handle = WindowHandle::XlibHandle(event.window);
+
+    if event.message_type == xw.atoms.NetWMDesktop {
+        let value = event.data.get_long(0);
+        return match usize::try_from(value) {
+            Ok(index) => Some(DisplayEvent::WindowSendToTag(handle, index + 1)),
+            Err(err) => {
+                log::debug!(
+                    "Received invalid value for window desktop ({}): {}",
+                    value,
+                    err
+                );
+                None
+            }
+        };
+    }
+
+    if event.message_type == xw.atoms.NetActiveWindow {
+        // Requests from pagers (source 2) are direct user actions.
+        let time = match event.data.get_long(0) {
+            2 => None,
+            _ => Some(event.data.get_long(1) as u64),
+        };
+        return Some(DisplayEvent::WindowActivate(handle, time));
+    }
+
+    if event.message_type == xw.atoms.NetCloseWindow {
+        return Some(DisplayEvent::WindowClose(handle));
+    }
+
+    if event.message_type == xw.atoms.NetMoveResizeWindow {
+        // Bits 8 to 11 of the flags tell which of x, y, width and height are set.
+        let flags = event.data.get_long(0);
+        let value = |index: usize| {
+            (flags & (1 << (7 + index)) != 0).then(|| event.data.get_long(index) as i32)
+        };
+        let mut change = WindowChange::new(handle);
+        change.floating = Some(XyhwChange {
+            x: value(1),
+            y: value(2),
+            w: value(3),
+            h: value(4),
+            ..XyhwChange::default()
+        });
+        return Some(DisplayEvent::WindowChange(change));
+    }
+
+    if event.message_type == xw.atoms.NetWMMoveResize {
+        // Directions 0 to 7 are the edges and corners, 8 is a move. Keyboard driven moves and
+        // resizes are not supported.
+        return match event.data.get_long(2) {
+            0..=7 => Some(DisplayEvent::StartResizingWindow(handle)),
+            8 => Some(DisplayEvent::StartMovingWindow(handle)),
+            _ => None,
+        };
+    }
+
+    if event.message_type == xw.atoms.NetRestackWindow {
+        return match event.data.get_long(2) as i32 {
+            xlib::Below | xlib::BottomIf => Some(DisplayEvent::LowerWindow(handle)),
+            _ => Some(DisplayEvent::RaiseWindow(handle)),
+        };
+    }
+
+    if event.message_type == xw.atoms.NetWMState {
+        return Some(from_net_wm_state(xw, event));
+    }
+
+    None
+}
+
+fn from_net_wm_state(xw: &XWrap, event: xlib::XClientMessageEvent) -> DisplayEvent {
+    //if the client is trying to change its state without changing the window state, change it too
+    let action = event.data.get_long(0);
+    let mut states = xw.get_window_states_atoms(event.window);
+    for index in 1..=2 {
+        let atom = event.data.get_long(index) as xlib::Atom;
+        if atom == 0 || !managed_states(xw).contains(&atom) {
+            continue;
+        }
         //determine what to change the state to
-        let fullscreen = if toggle_fullscreen {
-            !states.contains(&xw.atoms.NetWMStateFullscreen)
-        } else {
-            set_fullscreen
+        let set = match action {
+            2 => !states.contains(&atom),
+            _ => action == 1,
         };
         //update the list of states
-        if fullscreen {
-            states.push(xw.atoms.NetWMStateFullscreen);
+        if set {
+            states.push(atom);
         } else {
-            states.retain(|x| x != &xw.atoms.NetWMStateFullscreen);
+            states.retain(|x| x != &atom);
         }
-        states.sort_unstable();
-        states.dedup();
-        //set the windows state
-        xw.set_window_states_atoms(event.window, &states);
     }
+    states.sort_unstable();
+    states.dedup();
+    //set the windows state
+    xw.set_window_states_atoms(event.window, &states);
 
     //update the window states
-    if event.message_type == xw.atoms.NetWMState {
-        let handle = WindowHandle::XlibHandle(event.window);
-        let mut change = WindowChange::new(handle);
-        let states = xw.get_window_states(event.window);
-        change.states = Some(states);
-        return Some(DisplayEvent::WindowChange(change));
-    }
+    let mut change = WindowChange::new(WindowHandle::XlibHandle(event.window));
+    change.states = Some(xw.get_window_states(event.window));
+    DisplayEvent::WindowChange(change)
+}
 
-    None
+/// The `_NET_WM_STATE` atoms a client may ask the window manager to change.
+fn managed_states(xw: &XWrap) -> [xlib::Atom; 5] {
+    [
+        xw.atoms.NetWMStateFullscreen,
+        xw.atoms.NetWMStateMaximizedVert,
+        xw.atoms.NetWMStateMaximizedHorz,
+        xw.atoms.NetWMStateAbove,
+        xw.atoms.NetWMStateBelow,
+    ]
 }
@@ -58,6 +58,13 @@ pub fn from_event(xw: &XWrap, event: xlib::XPropertyEvent) -> Option<DisplayEven
                 }
             }
 
+            if event.atom == xw.atoms.NetWMUserTime {
+                let handle = WindowHandle::XlibHandle(event.window);
+                let mut change = WindowChange::new(handle);
+                change.user_time = xw.get_window_user_time(event.window);
+                return Some(DisplayEvent::WindowChange(change));
+            }
+
             if event.atom == xw.atoms.NetWMState {
                 let handle = WindowHandle::XlibHandle(event.window);
                 let mut change = WindowChange::new(handle);
@@ -71,7 +78,10 @@ pub fn from_event(xw: &XWrap, event: xlib::XPropertyEvent) -> Option<DisplayEven
     }
 }
 
-fn build_change_for_size_strut_partial(xw: &XWrap, window: xlib::Window) -> Option<WindowChange> {
+pub fn build_change_for_size_strut_partial(
+    xw: &XWrap,
+    window: xlib::Window,
+) -> Option<WindowChange> {
     let handle = WindowHandle::XlibHandle(window);
     let mut change = WindowChange::new(handle);
     let r#type = xw.get_window_type(window);
@@ -102,6 +112,7 @@ fn build_change_for_size_strut_partial(xw: &XWrap, window: xlib::Window) -> Opti
 fn build_change_for_size_hints(xw: &XWrap, window: xlib::Window) -> Option<WindowChange> {
     let handle = WindowHandle::XlibHandle(window);
     let mut change = WindowChange::new(handle);
+    change.size_hints = xw.get_size_hints(window);
     let hint = xw.get_hint_sizing_as_xyhw(window)?;
     if hint.x.is_none() && hint.y.is_none() && hint.w.is_none() && hint.h.is_none() {
         //junk hint; change change anything
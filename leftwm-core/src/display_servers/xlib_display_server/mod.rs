@@ -1,10 +1,11 @@
-use crate::config::Config;
+use crate::config::{self, Config};
 use crate::display_action::DisplayAction;
 use crate::models::Mode;
 use crate::models::Screen;
 use crate::models::Window;
 use crate::models::WindowHandle;
 use crate::models::WindowState;
+use crate::models::WindowType;
 use crate::models::Workspace;
 use crate::state::State;
 use crate::utils;
@@ -22,6 +23,7 @@ mod xwrap;
 pub use xwrap::XWrap;
 
 use event_translate::XEvent;
+use event_translate_property_notify::build_change_for_size_strut_partial;
 mod xcursor;
 
 pub struct XlibDisplayServer {
@@ -87,10 +89,11 @@ impl DisplayServer for XlibDisplayServer {
         }
     }
 
-    fn update_workspaces(&self, _workspaces: Vec<&Workspace>, focused: Option<&Workspace>) {
+    fn update_workspaces(&self, workspaces: Vec<&Workspace>, focused: Option<&Workspace>) {
         if let Some(focused) = focused {
             self.xw.set_current_desktop(&focused.tags);
         }
+        self.xw.set_workarea(&workspaces);
     }
 
     fn get_next_events(&mut self) -> Vec<DisplayEvent> {
@@ -113,6 +116,14 @@ impl DisplayServer for XlibDisplayServer {
             }
         }
 
+        // Docks reserve space relative to the screens, so their struts must follow them.
+        if events
+            .iter()
+            .any(|e| matches!(e, DisplayEvent::ScreensChanged(_)))
+        {
+            events.extend(self.dock_changes());
+        }
+
         for event in &events {
             if let DisplayEvent::WindowDestroy(WindowHandle::XlibHandle(w)) = event {
                 self.xw.force_unmapped(*w);
@@ -173,11 +184,13 @@ impl DisplayServer for XlibDisplayServer {
                     WindowState::Fullscreen => self.xw.atoms.NetWMStateFullscreen,
                     WindowState::Above => self.xw.atoms.NetWMStateAbove,
                     WindowState::Below => self.xw.atoms.NetWMStateBelow,
+                    WindowState::DemandsAttention => self.xw.atoms.NetWMStateDemandsAttention,
                 };
                 self.xw.set_state(h, toggle_to, state);
                 None
             }
             DisplayAction::SetWindowOrder(windows) => {
+                self.xw.set_client_list_stacking(&windows);
                 // The windows we are managing should be behind unmanaged windows. Unless they are
                 // fullscreen, or their children.
                 let (fullscreen_windows, other): (Vec<&Window>, Vec<&Window>) =
@@ -279,7 +292,7 @@ impl XlibDisplayServer {
     fn initial_events(&self, config: &impl Config) -> Vec<DisplayEvent> {
         let mut events = vec![];
         if let Some(workspaces) = config.workspaces() {
-            if workspaces.is_empty() {
+            if config::Workspace::follow_monitors(&workspaces) {
                 // tell manager about existing screens
                 self.xw.get_screens().into_iter().for_each(|screen| {
                     let e = DisplayEvent::ScreenCreate(screen);
@@ -330,6 +343,16 @@ impl XlibDisplayServer {
         }
         all
     }
+    /// Returns the new location of every dock, after the screens changed.
+    fn dock_changes(&self) -> Vec<DisplayEvent> {
+        let handles = self.xw.get_all_windows().unwrap_or_default();
+        handles
+            .into_iter()
+            .filter(|handle| self.xw.get_window_type(*handle) == WindowType::Dock)
+            .filter_map(|handle| build_change_for_size_strut_partial(&self.xw, handle))
+            .map(DisplayEvent::WindowChange)
+            .collect()
+    }
 }
 
 //return an offset to hide the window in the right, if it should be hidden on the right
@@ -62,6 +62,16 @@ pub struct XAtom {
     pub NetWMDesktop: xlib::Atom,
     pub NetWMStrutPartial: xlib::Atom, //net version - Reserve Screen Space
     pub NetWMStrut: xlib::Atom,        //old version
+    pub NetCloseWindow: xlib::Atom,
+    pub NetMoveResizeWindow: xlib::Atom,
+    pub NetWMMoveResize: xlib::Atom,
+    pub NetRestackWindow: xlib::Atom,
+    pub NetWMUserTime: xlib::Atom,
+    pub NetWorkarea: xlib::Atom,
+    pub NetDesktopGeometry: xlib::Atom,
+    pub NetClientListStacking: xlib::Atom,
+    pub NetFrameExtents: xlib::Atom,
+    pub NetShowingDesktop: xlib::Atom,
 
     pub UTF8String: xlib::Atom,
 }
@@ -114,6 +124,16 @@ impl XAtom {
             self.NetWMDesktop,
             self.NetWMStrutPartial,
             self.NetWMStrut,
+            self.NetCloseWindow,
+            self.NetMoveResizeWindow,
+            self.NetWMMoveResize,
+            self.NetRestackWindow,
+            self.NetWMUserTime,
+            self.NetWorkarea,
+            self.NetDesktopGeometry,
+            self.NetClientListStacking,
+            self.NetFrameExtents,
+            self.NetShowingDesktop,
         ]
     }
 
@@ -252,6 +272,36 @@ impl XAtom {
         if atom == self.NetWMStrut {
             return "_NET_WM_STRUT";
         }
+        if atom == self.NetCloseWindow {
+            return "_NET_CLOSE_WINDOW";
+        }
+        if atom == self.NetMoveResizeWindow {
+            return "_NET_MOVERESIZE_WINDOW";
+        }
+        if atom == self.NetWMMoveResize {
+            return "_NET_WM_MOVERESIZE";
+        }
+        if atom == self.NetRestackWindow {
+            return "_NET_RESTACK_WINDOW";
+        }
+        if atom == self.NetWMUserTime {
+            return "_NET_WM_USER_TIME";
+        }
+        if atom == self.NetWorkarea {
+            return "_NET_WORKAREA";
+        }
+        if atom == self.NetDesktopGeometry {
+            return "_NET_DESKTOP_GEOMETRY";
+        }
+        if atom == self.NetClientListStacking {
+            return "_NET_CLIENT_LIST_STACKING";
+        }
+        if atom == self.NetFrameExtents {
+            return "_NET_FRAME_EXTENTS";
+        }
+        if atom == self.NetShowingDesktop {
+            return "_NET_SHOWING_DESKTOP";
+        }
 
         if atom == self.UTF8String {
             return "UTF8_STRING";
@@ -315,6 +365,16 @@ impl XAtom {
             NetWMDesktop: from(xlib, dpy, "_NET_WM_DESKTOP"),
             NetWMStrutPartial: from(xlib, dpy, "_NET_WM_STRUT_PARTIAL"),
             NetWMStrut: from(xlib, dpy, "_NET_WM_STRUT"),
+            NetCloseWindow: from(xlib, dpy, "_NET_CLOSE_WINDOW"),
+            NetMoveResizeWindow: from(xlib, dpy, "_NET_MOVERESIZE_WINDOW"),
+            NetWMMoveResize: from(xlib, dpy, "_NET_WM_MOVERESIZE"),
+            NetRestackWindow: from(xlib, dpy, "_NET_RESTACK_WINDOW"),
+            NetWMUserTime: from(xlib, dpy, "_NET_WM_USER_TIME"),
+            NetWorkarea: from(xlib, dpy, "_NET_WORKAREA"),
+            NetDesktopGeometry: from(xlib, dpy, "_NET_DESKTOP_GEOMETRY"),
+            NetClientListStacking: from(xlib, dpy, "_NET_CLIENT_LIST_STACKING"),
+            NetFrameExtents: from(xlib, dpy, "_NET_FRAME_EXTENTS"),
+            NetShowingDesktop: from(xlib, dpy, "_NET_SHOWING_DESKTOP"),
 
             UTF8String: from(xlib, dpy, "UTF8_STRING"),
         }
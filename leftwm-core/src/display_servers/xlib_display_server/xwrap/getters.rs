@@ -1,8 +1,8 @@
 //! `XWrap` getters.
 use super::{Screen, WindowHandle, XlibError, MAX_PROPERTY_VALUE_LEN};
-use crate::models::{DockArea, WindowState, WindowType, XyhwChange};
+use crate::models::{BBox, DockArea, SizeHints, WindowState, WindowType, XyhwChange};
 use crate::XWrap;
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
 use std::os::raw::{c_int, c_long, c_uchar, c_uint, c_ulong};
 use std::slice;
 use x11_dl::xlib;
@@ -180,6 +180,42 @@ impl XWrap {
         None
     }
 
+    /// Returns the `WM_SIZE_HINTS`/`WM_NORMAL_HINTS` of a window as `SizeHints`.
+    #[must_use]
+    pub fn get_size_hints(&self, window: xlib::Window) -> Option<SizeHints> {
+        let size = self.get_hint_sizing(window)?;
+        let mut hints = SizeHints::default();
+        if (size.flags & xlib::PBaseSize) != 0 {
+            hints.base_w = size.base_width;
+            hints.base_h = size.base_height;
+        } else if (size.flags & xlib::PMinSize) != 0 {
+            // The min size is used as the base size when no base size is given.
+            hints.base_w = size.min_width;
+            hints.base_h = size.min_height;
+        }
+        if (size.flags & xlib::PResizeInc) != 0 {
+            hints.inc_w = size.width_inc;
+            hints.inc_h = size.height_inc;
+        }
+        if (size.flags & xlib::PMaxSize) != 0 {
+            hints.max_w = size.max_width;
+            hints.max_h = size.max_height;
+        }
+        if (size.flags & xlib::PMinSize) != 0 {
+            hints.min_w = size.min_width;
+            hints.min_h = size.min_height;
+        } else if (size.flags & xlib::PBaseSize) != 0 {
+            // The base size is used as the min size when no min size is given.
+            hints.min_w = size.base_width;
+            hints.min_h = size.base_height;
+        }
+        if (size.flags & xlib::PAspect) != 0 && size.min_aspect.y > 0 && size.max_aspect.y > 0 {
+            hints.min_aspect = size.min_aspect.x as f32 / size.min_aspect.y as f32;
+            hints.max_aspect = size.max_aspect.x as f32 / size.max_aspect.y as f32;
+        }
+        Some(hints)
+    }
+
     /// Returns the next `Xevent` of the xserver.
     // `XNextEvent`: https://tronche.com/gui/x/xlib/event-handling/manipulating-event-queue/XNextEvent.html
     #[must_use]
@@ -215,6 +251,7 @@ impl XWrap {
                 .map(|i| {
                     let mut s = Screen::from(i);
                     s.root = root;
+                    s.output = self.get_output_name(&s.bbox);
                     s
                 })
                 .collect()
@@ -230,6 +267,48 @@ impl XWrap {
         }
     }
 
+    /// Returns the name of the `RandR` output (like `DP-2`) displaying the given area.
+    // `XRRGetScreenResourcesCurrent`: https://www.x.org/releases/current/doc/man/man3/Xrandr.3.xhtml
+    #[must_use]
+    pub fn get_output_name(&self, bbox: &BBox) -> Option<String> {
+        let xrandr = self.xrandr.as_ref()?;
+        let mut name = None;
+        unsafe {
+            let resources = (xrandr.XRRGetScreenResourcesCurrent)(self.display, self.root);
+            if resources.is_null() {
+                return None;
+            }
+            let crtcs = slice::from_raw_parts((*resources).crtcs, (*resources).ncrtc as usize);
+            for crtc in crtcs {
+                let info = (xrandr.XRRGetCrtcInfo)(self.display, resources, *crtc);
+                if info.is_null() {
+                    continue;
+                }
+                let crtc_box = BBox {
+                    x: (*info).x,
+                    y: (*info).y,
+                    width: (*info).width as i32,
+                    height: (*info).height as i32,
+                };
+                if (*info).noutput > 0 && crtc_box == *bbox {
+                    let output =
+                        (xrandr.XRRGetOutputInfo)(self.display, resources, *(*info).outputs);
+                    if !output.is_null() {
+                        let raw = CStr::from_ptr((*output).name);
+                        name = Some(raw.to_string_lossy().into_owned());
+                        (xrandr.XRRFreeOutputInfo)(output);
+                    }
+                }
+                (xrandr.XRRFreeCrtcInfo)(info);
+                if name.is_some() {
+                    break;
+                }
+            }
+            (xrandr.XRRFreeScreenResources)(resources);
+        }
+        name
+    }
+
     /// Returns the dimensions of the screens.
     #[must_use]
     pub fn get_screens_area_dimensions(&self) -> (i32, i32) {
@@ -372,6 +451,45 @@ impl XWrap {
         }
     }
 
+    /// Returns the class of a window from its `WM_CLASS`.
+    // `XGetClassHint`: https://tronche.com/gui/x/xlib/ICC/client-to-window-manager/XGetClassHint.html
+    #[must_use]
+    pub fn get_window_class(&self, window: xlib::Window) -> Option<String> {
+        unsafe {
+            let mut class_hint: xlib::XClassHint = std::mem::zeroed();
+            if (self.xlib.XGetClassHint)(self.display, window, std::ptr::addr_of_mut!(class_hint))
+                == 0
+            {
+                return None;
+            }
+            let class = (!class_hint.res_class.is_null()).then(|| {
+                CStr::from_ptr(class_hint.res_class)
+                    .to_string_lossy()
+                    .into_owned()
+            });
+            if !class_hint.res_name.is_null() {
+                (self.xlib.XFree)(class_hint.res_name.cast());
+            }
+            if !class_hint.res_class.is_null() {
+                (self.xlib.XFree)(class_hint.res_class.cast());
+            }
+            class
+        }
+    }
+
+    /// Returns a windows `_NET_WM_USER_TIME`.
+    #[must_use]
+    pub fn get_window_user_time(&self, window: xlib::Window) -> Option<u64> {
+        let (prop_return, _) = self
+            .get_property(window, self.atoms.NetWMUserTime, xlib::XA_CARDINAL)
+            .ok()?;
+        unsafe {
+            #[allow(clippy::cast_ptr_alignment, clippy::useless_conversion)]
+            let time = u64::from(*prop_return.cast::<c_ulong>());
+            Some(time)
+        }
+    }
+
     /// Returns the states of a window.
     #[must_use]
     pub fn get_window_states(&self, window: xlib::Window) -> Vec<WindowState> {
@@ -389,6 +507,7 @@ impl XWrap {
                 x if x == &self.atoms.NetWMStateFullscreen => WindowState::Fullscreen,
                 x if x == &self.atoms.NetWMStateAbove => WindowState::Above,
                 x if x == &self.atoms.NetWMStateBelow => WindowState::Below,
+                x if x == &self.atoms.NetWMStateDemandsAttention => WindowState::DemandsAttention,
                 _ => WindowState::Modal,
             })
             .collect()
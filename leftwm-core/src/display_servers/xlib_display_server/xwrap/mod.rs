@@ -9,8 +9,8 @@
 use super::xatom::XAtom;
 use super::xcursor::XCursor;
 use super::{utils, Screen, Window, WindowHandle};
-use crate::config::Config;
-use crate::models::{FocusBehaviour, Mode};
+use crate::config::{Config, Workspace};
+use crate::models::{FocusBehaviour, Mode, TagMode};
 use crate::utils::xkeysym_lookup::ModMask;
 use std::ffi::CString;
 use std::os::raw::{c_char, c_double, c_int, c_long, c_short, c_ulong};
@@ -19,7 +19,7 @@ use std::{ptr, slice};
 use tokio::sync::{oneshot, Notify};
 use tokio::time::Duration;
 use x11_dl::xlib;
-use x11_dl::xrandr::Xrandr;
+use x11_dl::xrandr::{self, Xrandr};
 
 mod getters;
 mod keyboard;
@@ -59,6 +59,7 @@ pub struct XWrap {
     colors: Colors,
     pub managed_windows: Vec<xlib::Window>,
     pub tag_labels: Vec<String>,
+    pub tag_mode: TagMode,
     pub mode: Mode,
     pub focus_behaviour: FocusBehaviour,
     pub mouse_key_mask: ModMask,
@@ -67,6 +68,10 @@ pub struct XWrap {
     pub task_notify: Arc<Notify>,
     pub motion_event_limiter: c_ulong,
     pub refresh_rate: c_short,
+    /// `RandR`, opened once as it is used on every monitor change.
+    xrandr: Option<Xrandr>,
+    /// The first event type of `RandR`, when we listen to monitor changes.
+    pub randr_event_base: Option<c_int>,
 }
 
 impl Default for XWrap {
@@ -136,9 +141,16 @@ impl XWrap {
             active: 0,
         };
 
-        let refresh_rate = match Xrandr::open() {
+        let xrandr = match Xrandr::open() {
+            Ok(xrandr) => Some(xrandr),
+            Err(err) => {
+                log::warn!("Monitor changes won't be followed without RandR: {}", err);
+                None
+            }
+        };
+        let refresh_rate = match &xrandr {
             // Get the current refresh rate from xrandr if available.
-            Ok(xrandr) => unsafe {
+            Some(xrandr) => unsafe {
                 let screen_resources = (xrandr.XRRGetScreenResources)(display, root);
                 let crtcs = slice::from_raw_parts(
                     (*screen_resources).crtcs,
@@ -165,7 +177,7 @@ impl XWrap {
                     .max()
                     .unwrap_or(60)
             },
-            Err(_) => 60,
+            None => 60,
         };
 
         log::debug!("Refresh Rate: {}", refresh_rate);
@@ -179,6 +191,7 @@ impl XWrap {
             colors,
             managed_windows: vec![],
             tag_labels: vec![],
+            tag_mode: TagMode::default(),
             mode: Mode::Normal,
             focus_behaviour: FocusBehaviour::Sloppy,
             mouse_key_mask: 0,
@@ -187,6 +200,8 @@ impl XWrap {
             task_notify,
             motion_event_limiter: 0,
             refresh_rate,
+            xrandr,
+            randr_event_base: None,
         };
 
         // Check that another WM is not running.
@@ -290,8 +305,15 @@ impl XWrap {
             (self.xlib.XDeleteProperty)(self.display, root, self.atoms.NetClientList);
         }
 
+        // Screens are only followed when they aren't set by the config.
+        if matches!(config.workspaces(), Some(workspaces) if Workspace::follow_monitors(&workspaces))
+        {
+            self.subscribe_to_screen_changes();
+        }
+
         // EWMH compliance for desktops.
         self.tag_labels = config.create_list_of_tag_labels();
+        self.tag_mode = config.tag_mode();
         self.init_desktops_hints();
 
         self.reset_grabs(&config.mapped_bindings());
@@ -301,6 +323,37 @@ impl XWrap {
         }
     }
 
+    /// Listens to monitors being plugged, unplugged or reconfigured.
+    // `XRRSelectInput`: https://www.x.org/releases/current/doc/man/man3/Xrandr.3.xhtml
+    fn subscribe_to_screen_changes(&mut self) {
+        if let Some(xrandr) = &self.xrandr {
+            let mut event_base = 0;
+            let mut error_base = 0;
+            unsafe {
+                if (xrandr.XRRQueryExtension)(
+                    self.display,
+                    std::ptr::addr_of_mut!(event_base),
+                    std::ptr::addr_of_mut!(error_base),
+                ) == 0
+                {
+                    return;
+                }
+                (xrandr.XRRSelectInput)(self.display, self.root, xrandr::RRScreenChangeNotifyMask);
+            }
+            self.randr_event_base = Some(event_base);
+        }
+    }
+
+    /// Lets Xlib know about the new configuration of the screens, and updates the desktops.
+    pub fn update_screen_configuration(&self, event: &mut xlib::XEvent) {
+        if let Some(xrandr) = &self.xrandr {
+            unsafe {
+                (xrandr.XRRUpdateConfiguration)(event);
+            }
+        }
+        self.set_desktops();
+    }
+
     /// EWMH support used for bars such as polybar.
     ///  # Panics
     ///
@@ -308,14 +361,38 @@ impl XWrap {
     // `Xutf8TextListToTextProperty`: https://linux.die.net/man/3/xutf8textlisttotextproperty
     // `XSetTextProperty`: https://tronche.com/gui/x/xlib/ICC/client-to-window-manager/XSetTextProperty.html
     pub fn init_desktops_hints(&self) {
+        self.set_desktops();
+        // Set a current desktop.
+        let data = vec![0_u32, xlib::CurrentTime as u32];
+        self.set_desktop_prop(&data, self.atoms.NetCurrentDesktop);
+
+        // Set the WM NAME.
+        self.set_desktop_prop_string("LeftWM", self.atoms.NetWMName, self.atoms.UTF8String);
+
+        self.set_desktop_prop_string("LeftWM", self.atoms.WMClass, xlib::XA_STRING);
+
+        self.set_desktop_prop_c_ulong(
+            self.root as c_ulong,
+            self.atoms.NetSupportingWmCheck,
+            xlib::XA_WINDOW,
+        );
+
+        // We have no mode hiding the windows to show the desktop.
+        let data = vec![0_u32];
+        self.set_desktop_prop(&data, self.atoms.NetShowingDesktop);
+    }
+
+    /// Sets the number, names, viewports and size of the desktops, after the tags or the
+    /// screens changed.
+    ///  # Panics
+    ///
+    ///  Panics if a new Cstring cannot be formed
+    pub fn set_desktops(&self) {
         let tag_labels = &self.tag_labels;
         let tag_length = tag_labels.len();
         // Set the number of desktop.
         let data = vec![tag_length as u32];
         self.set_desktop_prop(&data, self.atoms.NetNumberOfDesktops);
-        // Set a current desktop.
-        let data = vec![0_u32, xlib::CurrentTime as u32];
-        self.set_desktop_prop(&data, self.atoms.NetCurrentDesktop);
         // Set desktop names.
         let mut text: xlib::XTextProperty = unsafe { std::mem::zeroed() };
         unsafe {
@@ -340,20 +417,9 @@ impl XWrap {
             );
         }
 
-        // Set the WM NAME.
-        self.set_desktop_prop_string("LeftWM", self.atoms.NetWMName, self.atoms.UTF8String);
-
-        self.set_desktop_prop_string("LeftWM", self.atoms.WMClass, xlib::XA_STRING);
-
-        self.set_desktop_prop_c_ulong(
-            self.root as c_ulong,
-            self.atoms.NetSupportingWmCheck,
-            xlib::XA_WINDOW,
-        );
-
-        // Set a viewport.
-        let data = vec![0_u32, 0_u32];
-        self.set_desktop_prop(&data, self.atoms.NetDesktopViewport);
+        // Set the viewports and size of the desktops.
+        self.set_desktop_viewport();
+        self.set_desktop_geometry();
     }
 
     /// Send a `XConfigureEvent` for a window to X.
@@ -1,6 +1,6 @@
 //! `XWrap` setters.
 use super::WindowHandle;
-use crate::models::TagId;
+use crate::models::{TagId, TagMode, Window, Workspace};
 use crate::XWrap;
 use std::ffi::CString;
 use std::os::raw::{c_long, c_ulong};
@@ -67,11 +67,22 @@ impl XWrap {
         }
     }
 
+    /// Sets the stacking client list to the given windows, ordered from top to bottom.
+    pub fn set_client_list_stacking(&self, windows: &[Window]) {
+        let list = client_list_stacking(windows, &self.managed_windows);
+        self.replace_property_long(
+            self.root,
+            self.atoms.NetClientListStacking,
+            xlib::XA_WINDOW,
+            &list,
+        );
+    }
+
     /// Sets the current desktop.
     pub fn set_current_desktop(&self, current_tags: &[TagId]) {
         let mut indexes: Vec<u32> = current_tags
             .iter()
-            .map(|tag| tag.to_owned() as u32 - 1)
+            .map(|tag| desktop_index(self.tag_mode, self.tag_labels.len(), *tag) as u32)
             .collect();
         if indexes.is_empty() {
             indexes.push(0);
@@ -79,21 +90,34 @@ impl XWrap {
         self.set_desktop_prop(&indexes, self.atoms.NetCurrentDesktop);
     }
 
-    // /// Sets the current viewport.
-    // fn set_current_viewport(&self, tags: Vec<&String>) {
-    //     let mut indexes: Vec<u32> = vec![];
-    //     for tag in tags {
-    //         for (i, mytag) in self.tags.iter().enumerate() {
-    //             if tag.contains(mytag) {
-    //                 indexes.push(i as u32);
-    //             }
-    //         }
-    //     }
-    //     if indexes.is_empty() {
-    //         indexes.push(0);
-    //     }
-    //     self.set_desktop_prop(&indexes, self.atoms.NetDesktopViewport);
-    // }
+    /// Sets the viewport of every desktop. We don't support large desktops, so they all start at
+    /// the origin.
+    pub fn set_desktop_viewport(&self) {
+        let data = vec![0_u32; self.tag_labels.len() * 2];
+        self.set_desktop_prop(&data, self.atoms.NetDesktopViewport);
+    }
+
+    /// Sets the size of the desktop, which spans over all the screens.
+    pub fn set_desktop_geometry(&self) {
+        let (height, width) = self.get_screens_area_dimensions();
+        let data = vec![width as u32, height as u32];
+        self.set_desktop_prop(&data, self.atoms.NetDesktopGeometry);
+    }
+
+    /// Sets the work area of every desktop, the part of its workspace that isn't covered by docks.
+    /// Desktops that aren't displayed get the work area of the first workspace.
+    pub fn set_workarea(&self, workspaces: &[&Workspace]) {
+        if let Some(data) = workarea(workspaces, self.tag_labels.len()) {
+            self.set_desktop_prop(&data, self.atoms.NetWorkarea);
+        }
+    }
+
+    /// Sets the `_NET_FRAME_EXTENTS` of a window, which only has a border as a frame.
+    pub fn set_frame_extents(&self, window: xlib::Window, border: i32) {
+        let border = c_long::from(border);
+        let data = vec![border, border, border, border];
+        self.replace_property_long(window, self.atoms.NetFrameExtents, xlib::XA_CARDINAL, &data);
+    }
 
     /// Sets a desktop property.
     pub fn set_desktop_prop(&self, data: &[u32], atom: c_ulong) {
@@ -161,7 +185,10 @@ impl XWrap {
 
     /// Sets what desktop a window is on.
     pub fn set_window_desktop(&self, window: xlib::Window, current_tags: &[TagId]) {
-        let mut indexes: Vec<c_long> = current_tags.iter().map(|tag| (tag - 1) as c_long).collect();
+        let mut indexes: Vec<c_long> = current_tags
+            .iter()
+            .map(|tag| desktop_index(self.tag_mode, self.tag_labels.len(), *tag) as c_long)
+            .collect();
         if indexes.is_empty() {
             indexes.push(0);
         }
@@ -179,3 +206,87 @@ impl XWrap {
         self.replace_property_long(window, self.atoms.WMState, self.atoms.WMState, states);
     }
 }
+
+/// The `_NET_CLIENT_LIST_STACKING` of the managed windows among `windows`, which are ordered
+/// from top to bottom while the EWMH list goes from bottom to top.
+fn client_list_stacking(windows: &[Window], managed_windows: &[xlib::Window]) -> Vec<c_long> {
+    windows
+        .iter()
+        .rev()
+        .filter_map(|w| match w.handle {
+            WindowHandle::XlibHandle(h) if managed_windows.contains(&h) => Some(h as c_long),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the EWMH desktop of a tag. With per monitor tags, the desktops are the tags of the
+/// focused monitor.
+fn desktop_index(tag_mode: TagMode, tag_count: usize, tag: TagId) -> usize {
+    match tag_mode {
+        TagMode::PerMonitor if tag_count > 0 => (tag - 1) % tag_count,
+        _ => tag - 1,
+    }
+}
+
+/// The `_NET_WORKAREA` of `tag_count` desktops, `None` without workspaces.
+fn workarea(workspaces: &[&Workspace], tag_count: usize) -> Option<Vec<u32>> {
+    let fallback = workspaces.first()?.xyhw_avoided();
+    let data = (1..=tag_count)
+        .flat_map(|tag| {
+            let area = workspaces
+                .iter()
+                .find(|ws| ws.has_tag(&tag))
+                .map_or(fallback, |ws| ws.xyhw_avoided());
+            [area.x(), area.y(), area.w(), area.h()].map(|v| v.max(0) as u32)
+        })
+        .collect();
+    Some(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layouts::Layout;
+    use crate::models::BBox;
+
+    fn workspace(x: i32, tag: TagId) -> Workspace {
+        let bbox = BBox {
+            x,
+            y: 0,
+            width: 100,
+            height: 50,
+        };
+        let mut ws = Workspace::new(None, bbox, Layout::default(), None);
+        ws.show_tag(&tag);
+        ws
+    }
+
+    #[test]
+    fn the_stacking_list_should_go_from_bottom_to_top_with_managed_windows_only() {
+        let windows: Vec<Window> = [1, 2, 3]
+            .iter()
+            .map(|h| Window::new(WindowHandle::XlibHandle(*h), None, None))
+            .chain(Some(Window::new(WindowHandle::MockHandle(4), None, None)))
+            .collect();
+        assert_eq!(client_list_stacking(&windows, &[1, 3]), vec![3, 1]);
+    }
+
+    #[test]
+    fn per_monitor_tags_should_share_the_desktops() {
+        assert_eq!(desktop_index(TagMode::Shared, 3, 5), 4);
+        assert_eq!(desktop_index(TagMode::PerMonitor, 3, 5), 1);
+        assert_eq!(desktop_index(TagMode::PerMonitor, 0, 5), 4);
+    }
+
+    #[test]
+    fn each_desktop_should_get_the_workarea_of_its_workspace() {
+        let first = workspace(0, 2);
+        let second = workspace(100, 3);
+        assert_eq!(workarea(&[], 3), None);
+        assert_eq!(
+            workarea(&[&first, &second], 3),
+            Some(vec![0, 0, 100, 50, 0, 0, 100, 50, 100, 0, 100, 50])
+        );
+    }
+}
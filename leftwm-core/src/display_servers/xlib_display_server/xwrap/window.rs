@@ -121,6 +121,7 @@ impl XWrap {
                 let unlock =
                     xlib::CWX | xlib::CWY | xlib::CWWidth | xlib::CWHeight | xlib::CWBorderWidth;
                 self.set_window_config(handle, changes, u32::from(unlock));
+                self.set_frame_extents(handle, window.border());
                 let w: u32 = window.width() as u32;
                 let h: u32 = window.height() as u32;
                 self.move_resize_window(handle, window.x(), window.y(), w, h);
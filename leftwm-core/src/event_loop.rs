@@ -1,9 +1,17 @@
+use crate::command_builder::CommandBuilder;
+use crate::utils::command_socket::{ManagerRequest, Reply};
+use crate::utils::hooks::HookRunner;
 use crate::{child_process::Nanny, config::Config, models::FocusBehaviour};
-use crate::{CommandPipe, DisplayServer, Manager, Mode, StateSocket, Window, Workspace};
+use crate::{
+    Command, CommandPipe, CommandSocket, ConfigWatcher, DisplayAction, DisplayEvent, DisplayServer,
+    EventTracker, Manager, Mode, StateSocket, Window, Workspace,
+};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{atomic::Ordering, Once};
 
 impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
+    #[allow(clippy::too_many_lines)]
     pub async fn event_loop(mut self) {
         let socket_file = place_runtime_file("current_state.sock")
             .expect("ERROR: couldn't create current_state.sock");
@@ -19,6 +27,16 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             .await
             .expect("ERROR: couldn't connect to commands.pipe");
 
+        let command_socket_file =
+            place_runtime_file("commands.sock").expect("ERROR: couldn't create commands.sock");
+        let mut command_socket = CommandSocket::new(command_socket_file)
+            .await
+            .expect("ERROR: couldn't listen on commands.sock");
+
+        let mut watched_config = WatchedConfig::new(&self.config);
+        let mut event_tracker = EventTracker::new(&self.state);
+        let mut hook_runner = HookRunner::default();
+
         //start the current theme
         let after_first_loop: Once = Once::new();
 
@@ -28,6 +46,11 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             if self.state.mode == Mode::Normal {
                 state_socket.write_manager_state(&self.state).await.ok();
             }
+            let hooks = self.config.hooks();
+            for event in event_tracker.update(&self.state) {
+                hook_runner.handle(&hooks, &self.state, &event, &mut self.children);
+                command_socket.publish(event);
+            }
             self.display_server.flush();
 
             let mut needs_update = false;
@@ -49,9 +72,41 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
                 Some(cmd) = command_pipe.read_command(), if event_buffer.is_empty() => {
                     needs_update = self.command_handler(&cmd) || needs_update;
                     self.display_server.load_config(&self.config);
+                    watched_config.update_after(&self.config, &cmd);
+                }
+                Some(pending) = command_socket.read_request(), if event_buffer.is_empty() => {
+                    let reply = match &pending.request {
+                        ManagerRequest::Command(command) => {
+                            let result = self.checked_command_handler(command);
+                            // Sequences and aliases that fail may have applied some of their
+                            // commands.
+                            let partly_applied = result.is_err()
+                                && matches!(command, Command::Sequence(_) | Command::Alias(_));
+                            needs_update = result == Ok(true) || partly_applied || needs_update;
+                            self.display_server.load_config(&self.config);
+                            watched_config.update_after(&self.config, command);
+                            Reply::from(&result)
+                        }
+                        ManagerRequest::Query(query) => Reply::from(self.query_handler(*query)),
+                    };
+                    pending.respond(reply);
+                }
+                () = hook_runner.wait_for_due(), if event_buffer.is_empty() => {
+                    hook_runner.run_due(&mut self.children);
+                    continue;
+                }
+                () = watched_config.changed(), if event_buffer.is_empty() => {
+                    needs_update = self.reload_config_from_files() || needs_update;
+                    watched_config.update(&self.config);
                 }
                 else => {
-                    event_buffer.drain(..).for_each(|event| needs_update = self.display_event_handler(event) || needs_update);
+                    for event in event_buffer.drain(..) {
+                        let command = self.command_of(&event);
+                        needs_update = self.display_event_handler(event) || needs_update;
+                        if let Some(command) = command {
+                            watched_config.update_after(&self.config, &command);
+                        }
+                    }
                 }
             }
 
@@ -122,6 +177,95 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     }
 }
 
+impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
+    /// Re-read the config after its files changed and apply it, including the key grabs.
+    fn reload_config_from_files(&mut self) -> bool {
+        if !self.config.reload_from_files() {
+            return false;
+        }
+        log::info!("Config files changed, reloading");
+        self.display_server.load_config(&self.config);
+        self.state
+            .actions
+            .push_back(DisplayAction::ReloadKeyGrabs(self.config.mapped_bindings()));
+        self.reload_config()
+    }
+
+    /// The command a display event runs, like the one bound to a key combination.
+    fn command_of(&self, event: &DisplayEvent) -> Option<Command> {
+        match event {
+            DisplayEvent::KeyCombo(mod_mask, xkeysym) => CommandBuilder::<C>::new(&self.config)
+                .xkeyevent(*mod_mask, *xkeysym)
+                .cloned(),
+            DisplayEvent::SendCommand(command) => Some(command.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// The files the config is read from, and their watcher.
+struct WatchedConfig {
+    files: Vec<PathBuf>,
+    watcher: Option<ConfigWatcher>,
+}
+
+impl WatchedConfig {
+    fn new(config: &impl Config) -> Self {
+        let files = config.watched_files();
+        let watcher = Self::watch(&files);
+        Self { files, watcher }
+    }
+
+    /// Watch the files of the config again if they changed.
+    fn update(&mut self, config: &impl Config) {
+        let files = config.watched_files();
+        if files != self.files {
+            self.watcher = Self::watch(&files);
+            self.files = files;
+        }
+    }
+
+    /// Watch the files of the config again if `command` can change them, like `LoadTheme`.
+    fn update_after(&mut self, config: &impl Config, command: &Command) {
+        if changes_config_files(command, config.aliases()) {
+            self.update(config);
+        }
+    }
+
+    fn watch(files: &[PathBuf]) -> Option<ConfigWatcher> {
+        ConfigWatcher::new(files)
+            .map_err(|err| log::error!("Cannot watch config files: {}", err))
+            .ok()
+    }
+
+    async fn changed(&mut self) {
+        match &mut self.watcher {
+            Some(watcher) => watcher.wait_for_change().await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+/// Whether `command` can change the files the config is read from, directly or through the
+/// sequences and aliases it runs.
+fn changes_config_files(command: &Command, aliases: &HashMap<String, Vec<Command>>) -> bool {
+    match command {
+        Command::SoftReload => true,
+        Command::Other(line) => matches!(
+            line.split_whitespace().next(),
+            Some("LoadTheme" | "UnloadTheme")
+        ),
+        Command::Sequence(commands) => commands
+            .iter()
+            .any(|command| changes_config_files(command, aliases)),
+        Command::Alias(name) => matches!(
+            crate::resolve_alias(aliases, name),
+            Ok(commands) if commands.iter().any(|command| changes_config_files(command, aliases))
+        ),
+        _ => false,
+    }
+}
+
 fn place_runtime_file<P>(path: P) -> std::io::Result<PathBuf>
 where
     P: AsRef<Path>,
@@ -133,3 +277,50 @@ async fn timeout(mills: u64) {
     use tokio::time::{sleep, Duration};
     sleep(Duration::from_millis(mills)).await;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Keybind;
+    use crate::utils::xkeysym_lookup;
+
+    #[test]
+    fn only_theme_and_reload_commands_should_change_the_config_files() {
+        let mut aliases = HashMap::new();
+        aliases.insert(
+            "dark".to_string(),
+            vec![Command::Other("LoadTheme /themes/dark.toml".to_string())],
+        );
+        let changes = |command: &str| changes_config_files(&command.parse().unwrap(), &aliases);
+        assert!(changes("LoadTheme /themes/light.toml"));
+        assert!(changes("UnloadTheme"));
+        assert!(changes("SoftReload"));
+        assert!(changes("Sequence 'GotoTag 1' UnloadTheme"));
+        assert!(changes("Alias dark"));
+        assert!(!changes("GotoTag 1"));
+        assert!(!changes("Execute leftwm-command LoadTheme"));
+        assert!(!changes("Alias missing"));
+    }
+
+    #[test]
+    fn keybinds_should_give_the_command_they_run() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        let theme = Command::Other("LoadTheme /themes/dark.toml".to_string());
+        manager.config.keybinds = vec![Keybind {
+            command: theme.clone(),
+            modifier: vec!["Mod4".to_string()],
+            key: "t".to_string(),
+        }];
+        let mask = xkeysym_lookup::into_modmask(&["Mod4".to_string()]);
+        let key = xkeysym_lookup::into_keysym("t").unwrap();
+        assert_eq!(
+            manager.command_of(&DisplayEvent::KeyCombo(mask, key)),
+            Some(theme)
+        );
+        let other_key = xkeysym_lookup::into_keysym("u").unwrap();
+        assert_eq!(
+            manager.command_of(&DisplayEvent::KeyCombo(mask, other_key)),
+            None
+        );
+    }
+}
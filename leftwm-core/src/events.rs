@@ -0,0 +1,275 @@
+//! Discrete changes of the manager, sent to the clients subscribed to them.
+//!
+//! Events are found by comparing the state after each run of the event loop with the previous
+//! one, so they describe what changed whatever caused it.
+use crate::layouts::Layout;
+use crate::models::dto::{window_id, WindowInfo, WorkspaceInfo};
+use crate::models::{Mode, WindowHandle, WindowState};
+use crate::state::State;
+use serde::{Deserialize, Serialize};
+
+/// A change of the manager, like `{"event": "TitleChanged", "window": 1234, "title": "vim"}`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "event")]
+pub enum Event {
+    WindowCreated {
+        window: WindowInfo,
+    },
+    WindowClosed {
+        window: u64,
+    },
+    /// The focused window changed, `window` is `None` when no window has the focus.
+    FocusChanged {
+        window: Option<WindowInfo>,
+    },
+    /// A workspace shows other tags.
+    TagChanged {
+        workspace: usize,
+        tags: Vec<String>,
+    },
+    LayoutChanged {
+        workspace: usize,
+        layout: Layout,
+    },
+    /// Another workspace has the focus.
+    WorkspaceChanged {
+        workspace: WorkspaceInfo,
+    },
+    TitleChanged {
+        window: u64,
+        title: Option<String>,
+    },
+    UrgencyChanged {
+        window: u64,
+        urgent: bool,
+    },
+    ModeChanged {
+        mode: Mode,
+    },
+}
+
+/// The type of an [`Event`], used by clients to choose the events they get.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    WindowCreated,
+    WindowClosed,
+    FocusChanged,
+    TagChanged,
+    LayoutChanged,
+    WorkspaceChanged,
+    TitleChanged,
+    UrgencyChanged,
+    ModeChanged,
+}
+
+impl Event {
+    #[must_use]
+    pub const fn kind(&self) -> EventKind {
+        match self {
+            Self::WindowCreated { .. } => EventKind::WindowCreated,
+            Self::WindowClosed { .. } => EventKind::WindowClosed,
+            Self::FocusChanged { .. } => EventKind::FocusChanged,
+            Self::TagChanged { .. } => EventKind::TagChanged,
+            Self::LayoutChanged { .. } => EventKind::LayoutChanged,
+            Self::WorkspaceChanged { .. } => EventKind::WorkspaceChanged,
+            Self::TitleChanged { .. } => EventKind::TitleChanged,
+            Self::UrgencyChanged { .. } => EventKind::UrgencyChanged,
+            Self::ModeChanged { .. } => EventKind::ModeChanged,
+        }
+    }
+}
+
+/// What events are compared on, for one window.
+#[derive(Debug, Clone, PartialEq)]
+struct WindowSnapshot {
+    handle: WindowHandle,
+    title: Option<String>,
+    urgent: bool,
+}
+
+/// The parts of the state events are about.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Snapshot {
+    windows: Vec<WindowSnapshot>,
+    focused_window: Option<WindowHandle>,
+    focused_workspace: Option<usize>,
+    workspaces: Vec<(Vec<String>, Layout)>,
+    mode: Mode,
+}
+
+impl From<&State> for Snapshot {
+    fn from(state: &State) -> Self {
+        let windows = state
+            .windows
+            .iter()
+            .map(|window| WindowSnapshot {
+                handle: window.handle,
+                title: window.name.clone(),
+                urgent: window.has_state(&WindowState::DemandsAttention),
+            })
+            .collect();
+        let workspaces = state
+            .workspaces
+            .iter()
+            .map(|ws| {
+                let tags = ws
+                    .tags
+                    .iter()
+                    .filter_map(|tag| state.tags.get(*tag))
+                    .map(|tag| tag.label.clone())
+                    .collect();
+                (tags, ws.layout)
+            })
+            .collect();
+        Self {
+            windows,
+            focused_window: state.focus_manager.window(&state.windows).map(|w| w.handle),
+            focused_workspace: state.focus_manager.workspace_history.front().copied(),
+            workspaces,
+            mode: state.mode,
+        }
+    }
+}
+
+/// Remembers the last state seen, to tell what changed since.
+#[derive(Debug, Default)]
+pub struct EventTracker {
+    last: Snapshot,
+}
+
+impl EventTracker {
+    #[must_use]
+    pub fn new(state: &State) -> Self {
+        Self { last: state.into() }
+    }
+
+    /// The events that happened since the last call.
+    pub fn update(&mut self, state: &State) -> Vec<Event> {
+        let current = Snapshot::from(state);
+        if current == self.last {
+            return vec![];
+        }
+        let mut events = vec![];
+        let last = std::mem::replace(&mut self.last, current);
+        let current = &self.last;
+
+        for window in &last.windows {
+            if !current.windows.iter().any(|w| w.handle == window.handle) {
+                events.push(Event::WindowClosed {
+                    window: window_id(window.handle),
+                });
+            }
+        }
+        for window in &current.windows {
+            let previous = last.windows.iter().find(|w| w.handle == window.handle);
+            match previous {
+                None => {
+                    if let Some(info) = window_info(state, window.handle) {
+                        events.push(Event::WindowCreated { window: info });
+                    }
+                }
+                Some(previous) => {
+                    if previous.title != window.title {
+                        events.push(Event::TitleChanged {
+                            window: window_id(window.handle),
+                            title: window.title.clone(),
+                        });
+                    }
+                    if previous.urgent != window.urgent {
+                        events.push(Event::UrgencyChanged {
+                            window: window_id(window.handle),
+                            urgent: window.urgent,
+                        });
+                    }
+                }
+            }
+        }
+        if last.focused_window != current.focused_window {
+            events.push(Event::FocusChanged {
+                window: current
+                    .focused_window
+                    .and_then(|handle| window_info(state, handle)),
+            });
+        }
+
+        for (index, (tags, layout)) in current.workspaces.iter().enumerate() {
+            let previous = last.workspaces.get(index);
+            if previous.map(|(tags, _)| tags) != Some(tags) {
+                events.push(Event::TagChanged {
+                    workspace: index,
+                    tags: tags.clone(),
+                });
+            }
+            if previous.map(|(_, layout)| layout) != Some(layout) {
+                events.push(Event::LayoutChanged {
+                    workspace: index,
+                    layout: *layout,
+                });
+            }
+        }
+        if last.focused_workspace != current.focused_workspace {
+            let workspace = current
+                .focused_workspace
+                .and_then(|index| Some((index, state.workspaces.get(index)?)));
+            if let Some((index, ws)) = workspace {
+                events.push(Event::WorkspaceChanged {
+                    workspace: WorkspaceInfo::new(state, index, ws),
+                });
+            }
+        }
+        if last.mode != current.mode {
+            events.push(Event::ModeChanged { mode: current.mode });
+        }
+        events
+    }
+}
+
+fn window_info(state: &State, handle: WindowHandle) -> Option<WindowInfo> {
+    let window = state.windows.iter().find(|w| w.handle == handle)?;
+    Some(WindowInfo::new(state, window))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Screen;
+    use crate::{Command, Manager, Window};
+
+    fn kinds(events: &[Event]) -> Vec<EventKind> {
+        events.iter().map(Event::kind).collect()
+    }
+
+    #[test]
+    fn changes_should_be_reported_once_as_events() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let mut tracker = EventTracker::new(&manager.state);
+
+        let window = Window::new(WindowHandle::MockHandle(1), Some("vim".to_string()), None);
+        manager.window_created_handler(window, -1, -1);
+        assert_eq!(
+            kinds(&tracker.update(&manager.state)),
+            vec![EventKind::WindowCreated, EventKind::FocusChanged]
+        );
+        assert!(tracker.update(&manager.state).is_empty());
+
+        manager.state.windows[0].name = Some("vim README.md".to_string());
+        let events = tracker.update(&manager.state);
+        assert!(matches!(
+            events.as_slice(),
+            [Event::TitleChanged { window: 1, title: Some(title) }] if title == "vim README.md"
+        ));
+
+        manager.command_handler(&Command::GotoTag(2));
+        assert_eq!(
+            kinds(&tracker.update(&manager.state)),
+            vec![EventKind::FocusChanged, EventKind::TagChanged]
+        );
+
+        manager.window_destroyed_handler(&WindowHandle::MockHandle(1));
+        assert_eq!(
+            kinds(&tracker.update(&manager.state)),
+            vec![EventKind::WindowClosed]
+        );
+    }
+}
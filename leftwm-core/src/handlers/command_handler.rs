@@ -6,14 +6,15 @@
 // https://github.com/rust-lang/rust-clippy/issues/6563
 
 use super::*;
-use crate::child_process::Children;
+use crate::command::{resolve_alias, CommandError};
 use crate::display_action::DisplayAction;
 use crate::display_servers::DisplayServer;
 use crate::layouts::Layout;
-use crate::models::{TagId, WindowState};
+use crate::models::{BBox, SnapPosition, SplitDirection, TagId, WindowState, Xyhw, XyhwBuilder};
 use crate::state::State;
+use crate::utils::child_process::{exec_shell, exec_shell_with_env};
 use crate::utils::helpers::relative_find;
-use crate::utils::{child_process::exec_shell, helpers};
+use crate::utils::{helpers, placeholders};
 use crate::{config::Config, models::FocusBehaviour};
 
 impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
@@ -26,6 +27,99 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     pub fn command_handler(&mut self, command: &Command) -> bool {
         process_internal(self, command).unwrap_or(false)
     }
+
+    /// Processes a command like [`Self::command_handler`], unless it cannot be applied to the
+    /// current state, like sending the focused window to a tag when no window is focused.
+    ///
+    /// Returns true if changes need to be rendered.
+    /// # Errors
+    ///
+    /// Returns why the command cannot be applied, leaving the state untouched. Sequences and
+    /// aliases stop at their first command that cannot be applied, keeping the changes of the
+    /// commands before it.
+    pub fn checked_command_handler(&mut self, command: &Command) -> Result<bool, CommandError> {
+        check_command(&self.state, command)?;
+        match command {
+            Command::Sequence(_) | Command::Alias(_) => {
+                let (changed, outcome) = run_sequence(self, command);
+                outcome.map(|()| changed)
+            }
+            Command::Other(command) => C::checked_command_handler(command, self),
+            _ => Ok(self.command_handler(command)),
+        }
+    }
+}
+
+/// Checks that the window, tag, workspace or scratchpad a command acts on exists.
+fn check_command(state: &State, command: &Command) -> Result<(), CommandError> {
+    let focused_window = state.focus_manager.window(&state.windows);
+    match command {
+        Command::GotoTag(tag) | Command::SendWindowToTag(tag) => {
+            // With per monitor tags, tags are numbered within the focused workspace.
+            let tags = state
+                .focus_manager
+                .workspace(&state.workspaces)
+                .map_or_else(
+                    || state.tags.len_normal(),
+                    |ws| state.workspace_tags(ws).len(),
+                );
+            if *tag == 0 || *tag > tags {
+                return Err(CommandError::NoSuchTag(*tag));
+            }
+        }
+        Command::SendWorkspaceToTag(ws_index, tag_index) => {
+            if *ws_index >= state.workspaces.len() {
+                return Err(CommandError::NoSuchWorkspace(*ws_index));
+            }
+            if *tag_index >= state.tags.len_normal() {
+                return Err(CommandError::NoSuchTag(tag_index + 1));
+            }
+        }
+        Command::ToggleScratchPad(name) if !state.scratchpads.iter().any(|s| &s.name == name) => {
+            return Err(CommandError::NoSuchScratchPad(name.clone()));
+        }
+        _ => {}
+    }
+    if acts_on_focused_window(command) && focused_window.is_none() {
+        return Err(CommandError::NoFocusedWindow);
+    }
+    let moves_floating = matches!(
+        command,
+        Command::MoveFloating(..)
+            | Command::ResizeFloating(..)
+            | Command::CenterFloating
+            | Command::SnapFloating(_)
+    );
+    if moves_floating && !matches!(focused_window, Some(window) if window.floating()) {
+        return Err(CommandError::NotFloating);
+    }
+    Ok(())
+}
+
+fn acts_on_focused_window(command: &Command) -> bool {
+    matches!(
+        command,
+        Command::CloseWindow
+            | Command::ToggleFullScreen
+            | Command::ToggleSticky
+            | Command::ToggleAbove
+            | Command::ToggleBelow
+            | Command::ToggleMaximize
+            | Command::SendWindowToTag(_)
+            | Command::MoveWindowToLastWorkspace
+            | Command::MoveWindowToNextWorkspace
+            | Command::MoveWindowToPreviousWorkspace
+            | Command::MoveWindowUp
+            | Command::MoveWindowDown
+            | Command::MoveWindowTop
+            | Command::FloatingToTile
+            | Command::TileToFloating
+            | Command::ToggleFloating
+            | Command::MoveFloating(..)
+            | Command::ResizeFloating(..)
+            | Command::CenterFloating
+            | Command::SnapFloating(_)
+    )
 }
 
 fn process_internal<C: Config, SERVER: DisplayServer>(
@@ -34,21 +128,24 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
 ) -> Option<bool> {
     let state = &mut manager.state;
     match command {
-        Command::Execute(shell_command) => execute(&mut manager.children, shell_command),
+        Command::Execute(shell_command) => execute(manager, shell_command),
 
         Command::ToggleScratchPad(name) => toggle_scratchpad(manager, name),
 
         Command::ToggleFullScreen => toggle_state(state, WindowState::Fullscreen),
         Command::ToggleSticky => toggle_state(state, WindowState::Sticky),
+        Command::ToggleAbove => toggle_layer(state, WindowState::Above, WindowState::Below),
+        Command::ToggleBelow => toggle_layer(state, WindowState::Below, WindowState::Above),
+        Command::ToggleMaximize => toggle_maximize(state),
 
-        Command::SendWindowToTag(tag) => move_to_tag(*tag, manager),
+        Command::SendWindowToTag(tag) => move_to_tag(state.monitor_tag(*tag), manager),
         Command::MoveWindowToNextWorkspace => move_window_to_workspace_change(manager, 1),
         Command::MoveWindowToPreviousWorkspace => move_window_to_workspace_change(manager, -1),
         Command::MoveWindowUp => move_focus_common_vars(move_window_change, state, -1),
         Command::MoveWindowDown => move_focus_common_vars(move_window_change, state, 1),
         Command::MoveWindowTop => move_focus_common_vars(move_window_top, state, 0),
 
-        Command::GotoTag(tag) => goto_tag(state, *tag),
+        Command::GotoTag(tag) => goto_tag(state, state.monitor_tag(*tag)),
 
         Command::CloseWindow => close_window(state),
         Command::SwapScreens => swap_tags(state),
@@ -102,12 +199,61 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
         Command::SendWorkspaceToTag(ws_index, tag_index) => {
             Some(send_workspace_to_tag(state, *ws_index, *tag_index))
         }
+        Command::MoveFloating(dx, dy) => move_floating(state, *dx, *dy),
+        Command::ResizeFloating(dw, dh) => resize_floating(state, *dw, *dh),
+        Command::CenterFloating => snap_floating(state, SnapPosition::Center),
+        Command::SnapFloating(position) => snap_floating(state, *position),
+        Command::ToggleStruts => toggle_workspace_flag(manager, |ws| &mut ws.ignore_struts),
+        Command::ToggleGutters => toggle_workspace_flag(manager, |ws| &mut ws.hide_gutters),
+        Command::ToggleDocks => toggle_workspace_flag(manager, |ws| &mut ws.hide_docks),
+        Command::ZenMode => zen_mode(manager),
+        Command::SplitWorkspace(direction, ratio) => split_workspace(manager, *direction, *ratio),
+        Command::MergeWorkspace => merge_workspace(manager),
+        Command::ResizeWorkspaceSplit(delta) => resize_workspace_split(manager, *delta),
+        Command::Sequence(_) | Command::Alias(_) => {
+            let (changed, outcome) = run_sequence(manager, command);
+            if let Err(err) = outcome {
+                log::warn!("Stopped running {:?}: {}", command, err);
+            }
+            Some(changed)
+        }
         Command::Other(cmd) => Some(C::command_handler(cmd, manager)),
     }
 }
 
-fn execute(children: &mut Children, shell_command: &str) -> Option<bool> {
-    let _ = exec_shell(shell_command, children);
+/// Runs the commands of a sequence or an alias in order, stopping at the first one that cannot
+/// be applied.
+///
+/// Returns whether changes need to be rendered, and why the commands stopped early.
+fn run_sequence<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    command: &Command,
+) -> (bool, Result<(), CommandError>) {
+    let commands = match command {
+        Command::Sequence(commands) => Ok(commands.clone()),
+        Command::Alias(name) => resolve_alias(manager.config.aliases(), name),
+        _ => Ok(vec![command.clone()]),
+    };
+    let commands = match commands {
+        Ok(commands) => commands,
+        Err(err) => return (false, Err(err)),
+    };
+    let mut changed = false;
+    for command in &commands {
+        match manager.checked_command_handler(command) {
+            Ok(command_changed) => changed = command_changed || changed,
+            Err(err) => return (true, Err(err)),
+        }
+    }
+    (changed, Ok(()))
+}
+
+fn execute<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    shell_command: &str,
+) -> Option<bool> {
+    let (shell_command, env) = placeholders::expand(shell_command, &manager.state);
+    let _ = exec_shell_with_env(&shell_command, &env, &mut manager.children);
     None
 }
 
@@ -207,9 +353,46 @@ fn toggle_state(state: &mut State, window_state: WindowState) -> Option<bool> {
     }
 }
 
+/// Toggles `layer` on the focused window, a window can't be both above and below the others.
+fn toggle_layer(state: &mut State, layer: WindowState, opposite: WindowState) -> Option<bool> {
+    let window = state.focus_manager.window(&state.windows)?;
+    let handle = window.handle;
+    if !window.has_state(&layer) && window.has_state(&opposite) {
+        let act = DisplayAction::SetState(handle, false, opposite);
+        state.actions.push_back(act);
+    }
+    toggle_state(state, layer)
+}
+
+fn toggle_maximize(state: &mut State) -> Option<bool> {
+    let window = state.focus_manager.window(&state.windows)?;
+    let handle = window.handle;
+    let toggle_to = !(window.has_state(&WindowState::MaximizedVert)
+        && window.has_state(&WindowState::MaximizedHorz));
+    for window_state in [WindowState::MaximizedVert, WindowState::MaximizedHorz] {
+        let act = DisplayAction::SetState(handle, toggle_to, window_state);
+        state.actions.push_back(act);
+    }
+    Some(true)
+}
+
 fn move_to_tag<C: Config, SERVER: DisplayServer>(
     tag_num: TagId,
     manager: &mut Manager<C, SERVER>,
+) -> Option<bool> {
+    let handle = manager
+        .state
+        .focus_manager
+        .window(&manager.state.windows)?
+        .handle;
+    move_window_to_tag(manager, handle, tag_num)
+}
+
+/// Moves a window to a tag, focusing the next window if it was the focused one.
+pub(crate) fn move_window_to_tag<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    handle: WindowHandle,
+    tag_num: TagId,
 ) -> Option<bool> {
     let tag = manager.state.tags.get(tag_num)?.clone();
 
@@ -220,18 +403,20 @@ fn move_to_tag<C: Config, SERVER: DisplayServer>(
         None => 1.0,
     };
 
-    let handle = manager
+    let was_focused = manager
         .state
         .focus_manager
-        .window(&manager.state.windows)?
-        .handle;
+        .window(&manager.state.windows)
+        .map(|w| w.handle)
+        == Some(handle);
     //Focus the next or previous window on the workspace
     let new_handle = manager.get_next_or_previous(&handle);
 
     let window = manager
         .state
-        .focus_manager
-        .window_mut(&mut manager.state.windows)?;
+        .windows
+        .iter_mut()
+        .find(|w| w.handle == handle)?;
     window.clear_tags();
     window.set_floating(false);
     window.tag(&tag.id);
@@ -240,6 +425,9 @@ fn move_to_tag<C: Config, SERVER: DisplayServer>(
     manager.state.actions.push_back(act);
 
     manager.state.sort_windows();
+    if !was_focused {
+        return Some(true);
+    }
     if let Some(new_handle) = new_handle {
         manager.state.focus_window(&new_handle);
     } else {
@@ -267,7 +455,15 @@ fn move_window_to_workspace_change<C: Config, SERVER: DisplayServer>(
 
 fn goto_tag(state: &mut State, input_tag: TagId) -> Option<bool> {
     let current_tag = state.focus_manager.tag(0).unwrap_or_default();
-    let previous_tag = state.focus_manager.tag(1).unwrap_or_default();
+    // with per monitor tags, the previous tag of the workspace
+    let previous_tag = state
+        .focus_manager
+        .tag_history
+        .iter()
+        .skip(1)
+        .find(|tag| state.tag_owner(**tag) == state.tag_owner(current_tag))
+        .copied()
+        .unwrap_or_default();
     let destination_tag = if !state.disable_current_tag_swap && current_tag == input_tag {
         previous_tag
     } else {
@@ -280,9 +476,10 @@ fn goto_tag(state: &mut State, input_tag: TagId) -> Option<bool> {
 /// A delta of 1 means "next tag", a delta of -1 means "previous tag".
 fn focus_tag_change(state: &mut State, delta: i8) -> Option<bool> {
     let current_tag = state.focus_manager.tag(0)?;
-    let tags = state.tags.normal();
-    let relative_tag_id = relative_find(tags, |tag| tag.id == current_tag, i32::from(delta), true)
-        .map(|tag| tag.id)?;
+    let workspace = state.focus_manager.workspace(&state.workspaces)?;
+    let tags = state.workspace_tags(workspace);
+    let relative_tag_id =
+        relative_find(&tags, |tag| *tag == current_tag, i32::from(delta), true).copied()?;
     state.goto_tag_handler(relative_tag_id)
 }
 
@@ -295,6 +492,11 @@ fn swap_tags(state: &mut State) -> Option<bool> {
         std::mem::swap(&mut state.workspaces.get_mut(hist_a)?.tags, &mut temp);
         std::mem::swap(&mut state.workspaces.get_mut(hist_b)?.tags, &mut temp);
         std::mem::swap(&mut state.workspaces.get_mut(hist_a)?.tags, &mut temp);
+        // With per monitor tags, the workspaces also swap the tags they own.
+        let offset_a = state.workspaces.get(hist_a)?.tag_offset;
+        let offset_b = state.workspaces.get(hist_b)?.tag_offset;
+        state.workspaces.get_mut(hist_a)?.tag_offset = offset_b;
+        state.workspaces.get_mut(hist_b)?.tag_offset = offset_a;
         // Update dock tags and layouts.
         state.update_static();
         state
@@ -311,11 +513,8 @@ fn swap_tags(state: &mut State) -> Option<bool> {
 }
 
 fn close_window(state: &mut State) -> Option<bool> {
-    let window = state.focus_manager.window(&state.windows)?;
-    if !window.is_unmanaged() {
-        let act = DisplayAction::KillWindow(window.handle);
-        state.actions.push_back(act);
-    }
+    let handle = state.focus_manager.window(&state.windows)?.handle;
+    state.close_window(&handle);
     None
 }
 
@@ -461,7 +660,9 @@ where
     let handle = state.focus_manager.window(&state.windows)?.handle;
     let tag_id = state.focus_manager.tag(0)?;
     let tag = state.tags.get(tag_id)?;
-    let (tags, layout) = (vec![tag_id], Some(tag.layout));
+    let zen = matches!(state.focus_manager.workspace(&state.workspaces), Some(ws) if ws.zen);
+    let layout = if zen { Layout::Monocle } else { tag.layout };
+    let (tags, layout) = (vec![tag_id], Some(layout));
 
     let for_active_workspace =
         |x: &Window| -> bool { helpers::intersect(&tags, &x.tags) && !x.is_unmanaged() };
@@ -602,7 +803,16 @@ fn change_main_width(state: &mut State, delta: i8, factor: i8) -> Option<bool> {
 fn set_margin_multiplier(state: &mut State, margin_multiplier: f32) -> Option<bool> {
     let ws = state.focus_manager.workspace_mut(&mut state.workspaces)?;
     ws.set_margin_multiplier(margin_multiplier);
-    let tags = ws.tags.clone();
+    apply_margin_multiplier(state)
+}
+
+/// Applies the margin multiplier of the focused workspace to its windows.
+fn apply_margin_multiplier(state: &mut State) -> Option<bool> {
+    let tags = state
+        .focus_manager
+        .workspace(&state.workspaces)?
+        .tags
+        .clone();
     if state.windows.iter().any(|w| w.r#type == WindowType::Normal) {
         let for_active_workspace = |x: &Window| -> bool {
             helpers::intersect(&tags, &x.tags) && x.r#type == WindowType::Normal
@@ -619,6 +829,59 @@ fn set_margin_multiplier(state: &mut State, margin_multiplier: f32) -> Option<bo
     Some(true)
 }
 
+/// Applies `change` to the location of the focused floating window. The result is kept inside
+/// the area of its workspace which is not covered by docks.
+fn update_floating<F>(state: &mut State, change: F) -> Option<bool>
+where
+    F: Fn(Xyhw, Xyhw) -> Xyhw,
+{
+    let window = state.focus_manager.window(&state.windows)?;
+    if !window.floating() || window.is_unmanaged() || window.is_fullscreen() {
+        return None;
+    }
+    let area = state
+        .workspaces
+        .iter()
+        .find(|ws| ws.is_displaying(window))
+        .or_else(|| state.focus_manager.workspace(&state.workspaces))?
+        .xyhw_avoided();
+    let xyhw = change(window.exact_xyhw(), area).clamped_to(&area);
+    let window = state.focus_manager.window_mut(&mut state.windows)?;
+    window.set_floating_exact(xyhw);
+    Some(true)
+}
+
+fn move_floating(state: &mut State, dx: i32, dy: i32) -> Option<bool> {
+    update_floating(state, |current, _| {
+        XyhwBuilder {
+            x: current.x() + dx,
+            y: current.y() + dy,
+            w: current.w(),
+            h: current.h(),
+            ..XyhwBuilder::default()
+        }
+        .into()
+    })
+}
+
+fn resize_floating(state: &mut State, dw: i32, dh: i32) -> Option<bool> {
+    // Windows are never drawn smaller than 100px, so don't shrink the offsets below that.
+    update_floating(state, |current, _| {
+        XyhwBuilder {
+            x: current.x(),
+            y: current.y(),
+            w: (current.w() + dw).max(100),
+            h: (current.h() + dh).max(100),
+            ..XyhwBuilder::default()
+        }
+        .into()
+    })
+}
+
+fn snap_floating(state: &mut State, position: SnapPosition) -> Option<bool> {
+    update_floating(state, |current, area| position.snap(&current, &area))
+}
+
 fn handle_focus(state: &mut State, handle: WindowHandle) -> bool {
     match state.focus_manager.behaviour {
         FocusBehaviour::Sloppy => {
@@ -641,10 +904,220 @@ fn send_workspace_to_tag(state: &mut State, ws_index: usize, tag_index: usize) -
     false
 }
 
+/// Flips a setting of the focused workspace, which changes the area covered by its windows.
+fn toggle_workspace_flag<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    flag: fn(&mut Workspace) -> &mut bool,
+) -> Option<bool> {
+    let ws = manager
+        .state
+        .focus_manager
+        .workspace_mut(&mut manager.state.workspaces)?;
+    let value = flag(ws);
+    *value = !*value;
+    manager.update_workspace_avoid_list();
+    Some(true)
+}
+
+/// Hides docks, gutters and margins of the focused workspace, only showing the focused window.
+fn zen_mode<C: Config, SERVER: DisplayServer>(manager: &mut Manager<C, SERVER>) -> Option<bool> {
+    toggle_workspace_flag(manager, |ws| &mut ws.zen)?;
+    let state = &mut manager.state;
+    apply_margin_multiplier(state);
+    // the first window is the one shown by the monocle layout
+    if let Some(handle) = state.focus_manager.window(&state.windows).map(|w| w.handle) {
+        state.move_to_top(&handle);
+    }
+    Some(true)
+}
+
+/// Divides the focused workspace in two, the new workspace gets what is left by `ratio` percent
+/// of the focused one. It is created like the workspace of a screen, but the screen itself is
+/// left as it is, so that the split survives the screens being applied again.
+fn split_workspace<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    direction: SplitDirection,
+    ratio: u8,
+) -> Option<bool> {
+    if ratio == 0 || ratio >= 100 {
+        return Some(false);
+    }
+    let state = &mut manager.state;
+    let index = *state.focus_manager.workspace_history.front()?;
+    let workspace = state.workspaces.get(index)?;
+    let output = workspace.output.clone();
+    let bbox = workspace.bbox();
+    let (kept, added) = match direction {
+        SplitDirection::Horizontal => {
+            let width = bbox.width * i32::from(ratio) / 100;
+            let added = BBox {
+                x: bbox.x + width,
+                width: bbox.width - width,
+                ..bbox
+            };
+            (BBox { width, ..bbox }, added)
+        }
+        SplitDirection::Vertical => {
+            let height = bbox.height * i32::from(ratio) / 100;
+            let added = BBox {
+                y: bbox.y + height,
+                height: bbox.height - height,
+                ..bbox
+            };
+            (BBox { height, ..bbox }, added)
+        }
+    };
+    let mut screen = state
+        .screens
+        .iter()
+        .find(|s| s.contains_bbox(&bbox))
+        .cloned()
+        .unwrap_or_else(|| Screen::new(bbox));
+    set_workspace_bbox(state, index, kept);
+
+    // The new workspace doesn't take the settings of the output.
+    screen.bbox = added;
+    screen.wsid = None;
+    screen.output = None;
+    manager.create_workspace(&screen);
+    if let Some(ws) = manager
+        .state
+        .focus_manager
+        .workspace_mut(&mut manager.state.workspaces)
+    {
+        ws.output = output;
+        ws.load_config(&manager.config);
+    }
+    manager.update_workspace_avoid_list();
+    Some(true)
+}
+
+/// Merges the focused workspace with the workspace it was split from, its windows are moved to
+/// the focused workspace.
+fn merge_workspace<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+) -> Option<bool> {
+    let state = &mut manager.state;
+    let index = *state.focus_manager.workspace_history.front()?;
+    let (other, merged) = split_neighbour(&state.workspaces, index)?;
+    let removed = state.remove_workspace(other);
+    let index = if other < index { index - 1 } else { index };
+    set_workspace_bbox(state, index, merged);
+    state.adopt_windows(&[removed]);
+    state.update_static();
+    manager.update_workspace_avoid_list();
+    Some(true)
+}
+
+/// Moves the border between the focused workspace and the workspace it was split from, by
+/// `delta` percent of their area.
+fn resize_workspace_split<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    delta: i8,
+) -> Option<bool> {
+    let state = &mut manager.state;
+    let index = *state.focus_manager.workspace_history.front()?;
+    let (other, merged) = split_neighbour(&state.workspaces, index)?;
+    let focused = state.workspaces[index].bbox();
+    let neighbour = state.workspaces[other].bbox();
+    let side_by_side = focused.y == neighbour.y && focused.height == neighbour.height;
+    let (start, total, size) = if side_by_side {
+        (merged.x, merged.width, focused.width)
+    } else {
+        (merged.y, merged.height, focused.height)
+    };
+    // each workspace keeps at least a tenth of the area
+    let size = (size + total * i32::from(delta) / 100).clamp(total / 10, total - total / 10);
+    let focused_first = if side_by_side {
+        focused.x < neighbour.x
+    } else {
+        focused.y < neighbour.y
+    };
+    let (focused_start, neighbour_start) = if focused_first {
+        (start, start + size)
+    } else {
+        (start + total - size, start)
+    };
+    let (focused, neighbour) = if side_by_side {
+        (
+            BBox {
+                x: focused_start,
+                width: size,
+                ..merged
+            },
+            BBox {
+                x: neighbour_start,
+                width: total - size,
+                ..merged
+            },
+        )
+    } else {
+        (
+            BBox {
+                y: focused_start,
+                height: size,
+                ..merged
+            },
+            BBox {
+                y: neighbour_start,
+                height: total - size,
+                ..merged
+            },
+        )
+    };
+    set_workspace_bbox(state, index, focused);
+    set_workspace_bbox(state, other, neighbour);
+    state.update_static();
+    manager.update_workspace_avoid_list();
+    Some(true)
+}
+
+/// Returns the workspace sharing a whole side with a workspace of the same output, and the area
+/// they cover together.
+fn split_neighbour(workspaces: &[Workspace], index: usize) -> Option<(usize, BBox)> {
+    let workspace = workspaces.get(index)?;
+    let a = workspace.bbox();
+    workspaces
+        .iter()
+        .enumerate()
+        .filter(|(i, ws)| *i != index && ws.output == workspace.output)
+        .find_map(|(i, ws)| {
+            let b = ws.bbox();
+            let side_by_side = a.y == b.y
+                && a.height == b.height
+                && (a.x + a.width == b.x || b.x + b.width == a.x);
+            let stacked = a.x == b.x
+                && a.width == b.width
+                && (a.y + a.height == b.y || b.y + b.height == a.y);
+            let merged = BBox {
+                x: a.x.min(b.x),
+                y: a.y.min(b.y),
+                width: if side_by_side {
+                    a.width + b.width
+                } else {
+                    a.width
+                },
+                height: if stacked {
+                    a.height + b.height
+                } else {
+                    a.height
+                },
+            };
+            (side_by_side || stacked).then_some((i, merged))
+        })
+}
+
+/// Moves and resizes a workspace within its screen.
+fn set_workspace_bbox(state: &mut State, index: usize, bbox: BBox) {
+    if let Some(workspace) = state.workspaces.get_mut(index) {
+        workspace.set_bbox(bbox);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::Tags;
+    use crate::models::{BBox, Tags};
 
     #[test]
     fn go_to_tag_should_return_false_if_no_screen_is_created() {
@@ -717,6 +1190,124 @@ mod tests {
         assert_eq!(manager.state.focus_manager.tag(3).unwrap_or_default(), 6);
     }
 
+    #[test]
+    fn per_monitor_tags_should_only_change_the_focused_monitor() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.state.tag_mode = crate::models::TagMode::PerMonitor;
+        manager.screen_create_handler(Screen::default());
+        manager.screen_create_handler(Screen::default());
+        // each monitor owns two tags
+        assert_eq!(manager.state.tags.len_normal(), 4);
+        assert_eq!(manager.state.workspaces[1].tag_offset, 2);
+        assert!(manager.state.workspaces[1].has_tag(&3));
+
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        assert!(manager.command_handler(&Command::SendWindowToTag(2)));
+        assert!(manager.state.windows[0].has_tag(&4));
+
+        assert!(manager.command_handler(&Command::GotoTag(2)));
+        assert!(manager.state.workspaces[0].has_tag(&1));
+        assert!(manager.state.workspaces[1].has_tag(&4));
+
+        // selecting a tag of the other monitor focuses it
+        assert!(manager.command_handler(&Command::GotoTag(3)));
+        assert!(manager.state.workspaces[1].has_tag(&3));
+        let focused = manager
+            .state
+            .focus_manager
+            .workspace(&manager.state.workspaces);
+        assert_eq!(focused.map(|ws| ws.tag_offset), Some(2));
+    }
+
+    #[test]
+    fn split_workspaces_can_be_resized_and_merged() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::new(BBox {
+            x: 0,
+            y: 0,
+            width: 3000,
+            height: 1000,
+        }));
+        let split = Command::SplitWorkspace(SplitDirection::Horizontal, 50);
+        assert!(manager.command_handler(&split));
+        assert_eq!(manager.state.workspaces.len(), 2);
+        assert_eq!(manager.state.workspaces[1].xyhw.x(), 1500);
+        assert!(manager.state.workspaces[1].has_tag(&2));
+
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        assert!(manager.command_handler(&Command::ResizeWorkspaceSplit(10)));
+        assert_eq!(manager.state.workspaces[0].xyhw.w(), 1200);
+        assert_eq!(manager.state.workspaces[1].xyhw.x(), 1200);
+        assert_eq!(manager.state.workspaces[1].xyhw.w(), 1800);
+
+        // the left workspace takes the windows of the right one
+        manager
+            .state
+            .focus_workspace(&manager.state.workspaces[0].clone());
+        assert!(manager.command_handler(&Command::MergeWorkspace));
+        assert_eq!(manager.state.workspaces.len(), 1);
+        assert_eq!(manager.state.workspaces[0].xyhw.w(), 3000);
+        assert!(manager.state.windows[0].has_tag(&1));
+        assert!(!manager.command_handler(&Command::MergeWorkspace));
+    }
+
+    #[test]
+    fn zen_mode_should_hide_docks_and_margins() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::new(BBox {
+            x: 0,
+            y: 0,
+            width: 1000,
+            height: 1000,
+        }));
+        let mut dock = Window::new(WindowHandle::MockHandle(1), None, None);
+        dock.r#type = WindowType::Dock;
+        dock.strut = Some(
+            XyhwBuilder {
+                w: 1000,
+                h: 20,
+                ..XyhwBuilder::default()
+            }
+            .into(),
+        );
+        manager.window_created_handler(dock, -1, -1);
+        manager.update_workspace_avoid_list();
+        for handle in 2..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(handle), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.state.focus_window(&WindowHandle::MockHandle(3));
+        assert_eq!(manager.state.workspaces[0].xyhw_avoided().y(), 20);
+
+        assert!(manager.command_handler(&Command::ZenMode));
+        manager.update_windows();
+        let workspace = &manager.state.workspaces[0];
+        assert_eq!(workspace.xyhw_avoided().y(), 0);
+        assert!(workspace.margin_multiplier().abs() < f32::EPSILON);
+        let visible: Vec<WindowHandle> = manager
+            .state
+            .windows
+            .iter()
+            .filter(|w| w.visible())
+            .map(|w| w.handle)
+            .collect();
+        assert_eq!(visible, vec![WindowHandle::MockHandle(3)]);
+
+        assert!(manager.command_handler(&Command::ZenMode));
+        assert_eq!(manager.state.workspaces[0].xyhw_avoided().y(), 20);
+    }
+
     #[test]
     fn focus_tag_change_should_go_to_previous_and_next_tag() {
         let mut manager = Manager::new_test(vec![
@@ -751,4 +1342,112 @@ mod tests {
         focus_tag_change(state, 13);
         assert_eq!(state.focus_manager.tag(0).unwrap(), 3);
     }
+
+    #[test]
+    fn move_floating_should_keep_the_window_inside_the_workspace() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::new(BBox {
+            x: 0,
+            y: 0,
+            width: 2000,
+            height: 1500,
+        }));
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+        assert!(manager.command_handler(&Command::TileToFloating));
+        assert!(manager.command_handler(&Command::SnapFloating(SnapPosition::TopLeft)));
+        assert!(manager.command_handler(&Command::MoveFloating(-50, 30)));
+        let window = manager.state.windows[0].exact_xyhw();
+        assert_eq!((window.x(), window.y()), (0, 30));
+
+        assert!(manager.command_handler(&Command::ResizeFloating(5000, 0)));
+        let window = manager.state.windows[0].exact_xyhw();
+        assert_eq!((window.x(), window.w()), (0, 2000));
+    }
+
+    #[test]
+    fn floating_commands_should_ignore_tiled_windows() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+        assert!(!manager.command_handler(&Command::CenterFloating));
+    }
+
+    #[test]
+    fn checked_commands_should_report_why_they_cannot_be_applied() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        assert_eq!(
+            manager.checked_command_handler(&Command::SendWindowToTag(2)),
+            Err(CommandError::NoFocusedWindow)
+        );
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+        assert_eq!(
+            manager.checked_command_handler(&Command::SendWindowToTag(12)),
+            Err(CommandError::NoSuchTag(12))
+        );
+        assert_eq!(
+            manager.checked_command_handler(&Command::CenterFloating),
+            Err(CommandError::NotFloating)
+        );
+        assert_eq!(
+            manager.checked_command_handler(&Command::ToggleScratchPad("term".to_string())),
+            Err(CommandError::NoSuchScratchPad("term".to_string()))
+        );
+        assert_eq!(
+            manager.checked_command_handler(&Command::SendWindowToTag(2)),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn sequences_should_stop_at_the_first_command_that_cannot_be_applied() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.config.aliases.insert(
+            "follow2".to_string(),
+            vec![Command::SendWindowToTag(2), Command::GotoTag(2)],
+        );
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+
+        assert_eq!(
+            manager.checked_command_handler(&Command::Alias("follow2".to_string())),
+            Ok(true)
+        );
+        assert_eq!(manager.state.windows[0].tags, vec![2]);
+        assert_eq!(manager.state.focus_manager.tag(0), Some(2));
+
+        let sequence = Command::Sequence(vec![
+            Command::GotoTag(1),
+            Command::GotoTag(12),
+            Command::GotoTag(2),
+        ]);
+        assert_eq!(
+            manager.checked_command_handler(&sequence),
+            Err(CommandError::NoSuchTag(12))
+        );
+        assert_eq!(manager.state.focus_manager.tag(0), Some(1));
+        assert_eq!(
+            manager.checked_command_handler(&Command::Alias("follow3".to_string())),
+            Err(CommandError::NoSuchAlias("follow3".to_string()))
+        );
+    }
 }
@@ -1,5 +1,6 @@
 use super::{CommandBuilder, Config, DisplayEvent, Manager, Mode};
 use crate::display_servers::DisplayServer;
+use crate::handlers::command_handler::move_window_to_tag;
 use crate::{display_action::DisplayAction, models::FocusBehaviour};
 
 impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
@@ -8,6 +9,7 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     pub fn display_event_handler(&mut self, event: DisplayEvent) -> bool {
         let update_needed = match event {
             DisplayEvent::ScreenCreate(s) => self.screen_create_handler(s),
+            DisplayEvent::ScreensChanged(screens) => self.screens_changed_handler(screens),
             DisplayEvent::WindowCreate(w, x, y) => self.window_created_handler(w, x, y),
             DisplayEvent::WindowChange(w) => self.window_changed_handler(w),
 
@@ -31,6 +33,10 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             },
 
             DisplayEvent::WindowDestroy(handle) => self.window_destroyed_handler(&handle),
+            DisplayEvent::WindowClose(handle) => {
+                self.state.close_window(&handle);
+                false
+            }
 
             DisplayEvent::KeyCombo(mod_mask, xkeysym) => {
                 //look through the config and build a command if its defined in the config
@@ -67,6 +73,22 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
 
             DisplayEvent::MoveWindow(handle, x, y) => self.window_move_handler(&handle, x, y),
             DisplayEvent::ResizeWindow(handle, x, y) => self.window_resize_handler(&handle, x, y),
+            DisplayEvent::StartMovingWindow(handle) => self.state.start_moving_window(handle),
+            DisplayEvent::StartResizingWindow(handle) => self.state.start_resizing_window(handle),
+
+            DisplayEvent::WindowActivate(handle, time) => self.state.activate_window(&handle, time),
+            DisplayEvent::WindowSendToTag(handle, tag) => {
+                let tag = self.state.monitor_tag(tag);
+                move_window_to_tag(self, handle, tag).unwrap_or(false)
+            }
+            DisplayEvent::RaiseWindow(handle) => {
+                self.state.move_to_top(&handle);
+                false
+            }
+            DisplayEvent::LowerWindow(handle) => {
+                self.state.move_to_bottom(&handle);
+                false
+            }
         };
 
         if update_needed {
@@ -76,3 +98,28 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         update_needed
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Screen, Window, WindowHandle};
+
+    #[test]
+    fn clients_should_be_able_to_close_windows() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let window = Window::new(WindowHandle::MockHandle(1), None, None);
+        manager.window_created_handler(window, -1, -1);
+        manager.state.actions.clear();
+
+        let close = DisplayEvent::WindowClose(WindowHandle::MockHandle(1));
+        manager.display_server.events.push(close);
+        for event in manager.display_server.get_next_events() {
+            manager.display_event_handler(event);
+        }
+        assert!(matches!(
+            manager.state.actions.pop_front(),
+            Some(DisplayAction::KillWindow(WindowHandle::MockHandle(1)))
+        ));
+    }
+}
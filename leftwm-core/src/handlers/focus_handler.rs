@@ -1,7 +1,7 @@
 #![allow(clippy::wildcard_imports)]
 
 use super::*;
-use crate::models::TagId;
+use crate::models::{FocusStealingPolicy, TagId, WindowState};
 use crate::state::State;
 use crate::{display_action::DisplayAction, models::FocusBehaviour};
 
@@ -48,6 +48,56 @@ impl State {
         true
     }
 
+    /// Focuses a window on behalf of a client or pager, showing its tag if it is hidden.
+    /// Requests from applications carry their user time and follow the focus stealing policy.
+    pub fn activate_window(&mut self, handle: &WindowHandle, time: Option<u64>) -> bool {
+        let Some(window) = self.windows.iter().find(|w| &w.handle == handle) else {
+            return false;
+        };
+        if let Some(time) = time {
+            // A request without a timestamp is as old as the last input of the window.
+            let time = Some(time).filter(|t| *t != 0).or(window.user_time);
+            if !self.may_steal_focus(window, time) {
+                self.mark_urgent(*handle);
+                return false;
+            }
+        }
+        let tag = if self.workspaces.iter().any(|ws| ws.is_displaying(window)) {
+            None
+        } else {
+            window.tags.first().copied()
+        };
+        if let Some(tag) = tag {
+            let _ = self.goto_tag_handler(tag);
+        }
+        self.move_to_top(handle);
+        self.focus_window(handle)
+    }
+
+    /// Whether a window may take the focus according to its focus stealing policy, `time` being
+    /// the user time of its request.
+    pub fn may_steal_focus(&self, window: &Window, time: Option<u64>) -> bool {
+        match self.focus_manager.stealing_policy(window) {
+            FocusStealingPolicy::Focus => true,
+            FocusStealingPolicy::Urgent => false,
+            // A user time of 0 means the window doesn't want the focus.
+            FocusStealingPolicy::Smart => match time {
+                Some(0) => false,
+                Some(time) => {
+                    let focused = self.focus_manager.window(&self.windows);
+                    !matches!(focused.and_then(|w| w.user_time), Some(last) if time < last)
+                }
+                None => true,
+            },
+        }
+    }
+
+    /// Asks the display server to mark a window as demanding attention.
+    pub fn mark_urgent(&mut self, handle: WindowHandle) {
+        let act = DisplayAction::SetState(handle, true, WindowState::DemandsAttention);
+        self.actions.push_back(act);
+    }
+
     pub fn focus_workspace_under_cursor(&mut self, x: i32, y: i32) -> bool {
         let focused_id = match self.focus_manager.workspace(&self.workspaces) {
             Some(fws) => fws.id,
@@ -192,6 +242,10 @@ fn focus_window_by_handle_work(state: &mut State, handle: &WindowHandle) -> Opti
         previous_handle: previous,
     };
     state.actions.push_back(act);
+    if found.has_state(&WindowState::DemandsAttention) {
+        let act = DisplayAction::SetState(*handle, false, WindowState::DemandsAttention);
+        state.actions.push_back(act);
+    }
 
     Some(found.clone())
 }
@@ -294,6 +348,72 @@ mod tests {
         assert_eq!(expected.handle, actual);
     }
 
+    #[test]
+    fn activating_a_hidden_window_should_show_its_tag() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let mut window = Window::new(WindowHandle::MockHandle(1), None, None);
+        window.tag(&2);
+        manager.state.windows.push(window);
+        manager
+            .state
+            .activate_window(&WindowHandle::MockHandle(1), None);
+        assert_eq!(manager.state.workspaces[0].tags, vec![2]);
+        let actual = manager
+            .state
+            .focus_manager
+            .window(&manager.state.windows)
+            .unwrap()
+            .handle;
+        assert_eq!(WindowHandle::MockHandle(1), actual);
+    }
+
+    #[test]
+    fn smart_focus_stealing_should_only_follow_newer_user_input() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.state.focus_manager.focus_stealing_policy = FocusStealingPolicy::Smart;
+        for (handle, user_time) in [(1, 100), (2, 50), (3, 200)] {
+            let mut window = Window::new(WindowHandle::MockHandle(handle), None, None);
+            window.user_time = Some(user_time);
+            manager.state.windows.push(window);
+        }
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+        let focused = |state: &State| state.focus_manager.window(&state.windows).unwrap().handle;
+
+        assert!(!manager
+            .state
+            .activate_window(&WindowHandle::MockHandle(2), Some(0)));
+        assert_eq!(focused(&manager.state), WindowHandle::MockHandle(1));
+        assert!(manager.state.actions.iter().any(|a| matches!(
+            a,
+            DisplayAction::SetState(
+                WindowHandle::MockHandle(2),
+                true,
+                WindowState::DemandsAttention
+            )
+        )));
+
+        manager
+            .state
+            .activate_window(&WindowHandle::MockHandle(3), Some(0));
+        assert_eq!(focused(&manager.state), WindowHandle::MockHandle(3));
+    }
+
+    #[test]
+    fn focus_stealing_policy_can_be_overridden_by_class() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager
+            .state
+            .focus_manager
+            .focus_stealing_overrides
+            .insert("Chat".to_string(), FocusStealingPolicy::Urgent);
+        let mut window = Window::new(WindowHandle::MockHandle(1), None, None);
+        assert!(manager.state.may_steal_focus(&window, None));
+        window.res_class = Some("Chat".to_string());
+        assert!(!manager.state.may_steal_focus(&window, None));
+    }
+
     #[test]
     fn focusing_the_same_window_shouldnt_add_to_the_history() {
         let mut manager = Manager::new_test(vec![]);
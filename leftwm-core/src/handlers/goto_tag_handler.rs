@@ -6,6 +6,12 @@ impl State {
             return Some(false);
         }
 
+        // with per monitor tags, the tag is shown on the workspace owning it
+        if let Some(owner) = self.tag_owner(tag_num) {
+            let workspace = self.workspaces[owner].clone();
+            self.focus_workspace(&workspace);
+        }
+
         //let tag_id = self.tags[tag_num - 1].label.clone();
         let new_tags = vec![tag_num];
         // No focus safety check.
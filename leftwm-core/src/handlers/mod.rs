@@ -3,7 +3,9 @@ pub mod display_event_handler;
 mod focus_handler;
 mod goto_tag_handler;
 mod mouse_combo_handler;
+mod query_handler;
 mod screen_create_handler;
+mod screens_changed_handler;
 mod window_handler;
 mod window_move_handler;
 mod window_resize_handler;
@@ -18,31 +18,61 @@ impl State {
         //look through the config and build a command if its defined in the config
         let act = self.build_action(modmask, button, handle, modifier);
         if let Some(act) = act {
-            //save off the info about position of the window when we started to move/resize
-            self.windows
-                .iter_mut()
-                .filter(|w| w.handle == handle)
-                .for_each(|w| {
-                    if w.floating() {
-                        let offset = w.get_floating_offsets().unwrap_or_default();
-                        w.start_loc = Some(offset);
-                    } else {
-                        let container = w.container_size.unwrap_or_default();
-                        let normal = w.normal;
-                        let floating = normal - container;
-                        w.set_floating_offsets(Some(floating));
-                        w.start_loc = Some(floating);
-                        w.set_floating(true);
-                    }
-                });
-            self.move_to_top(&handle);
-            self.actions.push_back(act);
+            self.start_drag(handle, act);
             return false;
         }
 
         true
     }
 
+    /// Starts moving a window on behalf of a client, e.g. when its title bar is dragged.
+    pub fn start_moving_window(&mut self, handle: WindowHandle) -> bool {
+        if self
+            .windows
+            .iter()
+            .any(|w| w.handle == handle && w.can_move())
+        {
+            self.mode = Mode::MovingWindow(handle);
+            self.start_drag(handle, DisplayAction::StartMovingWindow(handle));
+        }
+        false
+    }
+
+    /// Starts resizing a window on behalf of a client, e.g. when one of its edges is dragged.
+    pub fn start_resizing_window(&mut self, handle: WindowHandle) -> bool {
+        if self
+            .windows
+            .iter()
+            .any(|w| w.handle == handle && w.can_resize())
+        {
+            self.mode = Mode::ResizingWindow(handle);
+            self.start_drag(handle, DisplayAction::StartResizingWindow(handle));
+        }
+        false
+    }
+
+    fn start_drag(&mut self, handle: WindowHandle, act: DisplayAction) {
+        //save off the info about position of the window when we started to move/resize
+        self.windows
+            .iter_mut()
+            .filter(|w| w.handle == handle)
+            .for_each(|w| {
+                if w.floating() {
+                    let offset = w.get_floating_offsets().unwrap_or_default();
+                    w.start_loc = Some(offset);
+                } else {
+                    let container = w.container_size.unwrap_or_default();
+                    let normal = w.normal;
+                    let floating = normal - container;
+                    w.set_floating_offsets(Some(floating));
+                    w.start_loc = Some(floating);
+                    w.set_floating(true);
+                }
+            });
+        self.move_to_top(&handle);
+        self.actions.push_back(act);
+    }
+
     fn build_action(
         &mut self,
         mod_mask: ModMask,
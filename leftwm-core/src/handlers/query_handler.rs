@@ -0,0 +1,74 @@
+use super::Manager;
+use crate::config::Config;
+use crate::models::dto::{FocusedInfo, TagInfo, WindowInfo, WorkspaceInfo};
+use crate::query::Query;
+use serde_json::Value;
+
+impl<C: Config, SERVER> Manager<C, SERVER> {
+    /// Answers a query from the current state.
+    /// # Errors
+    ///
+    /// Will error if the answer cannot be serialized.
+    pub fn query_handler(&self, query: Query) -> serde_json::Result<Value> {
+        let state = &self.state;
+        match query {
+            Query::Windows => {
+                let windows: Vec<WindowInfo> = state
+                    .windows
+                    .iter()
+                    .map(|window| WindowInfo::new(state, window))
+                    .collect();
+                serde_json::to_value(windows)
+            }
+            Query::Workspaces => {
+                let workspaces: Vec<WorkspaceInfo> = state
+                    .workspaces
+                    .iter()
+                    .enumerate()
+                    .map(|(index, ws)| WorkspaceInfo::new(state, index, ws))
+                    .collect();
+                serde_json::to_value(workspaces)
+            }
+            Query::Tags => {
+                let tags: Vec<TagInfo> = state
+                    .tags
+                    .normal()
+                    .iter()
+                    .map(|tag| TagInfo::new(state, tag))
+                    .collect();
+                serde_json::to_value(tags)
+            }
+            Query::Focused => serde_json::to_value(FocusedInfo::from(state)),
+            Query::Keybinds => serde_json::to_value(self.config.mapped_bindings()),
+            Query::Layouts => serde_json::to_value(&state.layout_manager.layouts),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Screen, WindowHandle};
+    use crate::Window;
+
+    #[test]
+    fn windows_should_be_listed_with_their_tags() {
+        let mut manager = Manager::new_test(vec!["web".to_string(), "chat".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let mut window = Window::new(WindowHandle::MockHandle(7), Some("Firefox".into()), None);
+        window.res_class = Some("firefox".to_string());
+        manager.window_created_handler(window, -1, -1);
+
+        let windows = manager.query_handler(Query::Windows).unwrap();
+        assert_eq!(windows[0]["handle"], 7);
+        assert_eq!(windows[0]["class"], "firefox");
+        assert_eq!(windows[0]["tags"], serde_json::json!(["web"]));
+        assert_eq!(windows[0]["focused"], true);
+
+        let focused = manager.query_handler(Query::Focused).unwrap();
+        assert_eq!(focused["window"]["title"], "Firefox");
+        assert_eq!(focused["tag"]["label"], "web");
+        assert_eq!(focused["tag"]["windows"], 1);
+        assert_eq!(focused["workspace"]["index"], 0);
+    }
+}
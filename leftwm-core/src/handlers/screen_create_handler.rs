@@ -1,20 +1,43 @@
 use super::{Manager, Screen, Workspace};
 use crate::config::Config;
 use crate::display_servers::DisplayServer;
+use crate::models::{TagId, TagMode};
 
 impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     /// Process a collection of events, and apply them changes to a manager.
     ///
     /// Returns `true` if changes need to be rendered.
     pub fn screen_create_handler(&mut self, screen: Screen) -> bool {
-        let tag_index = self.state.workspaces.len();
+        self.create_workspace(&screen);
+        self.state.screens.push(screen);
+        false
+    }
+
+    /// Creates and focuses a workspace covering `screen`, without adding the screen itself, as
+    /// for the parts of a split screen.
+    pub(crate) fn create_workspace(&mut self, screen: &Screen) {
         let tag_len = self.state.tags.len_normal();
+        // workspaces configured for the output of the screen
+        let output_config = screen.output.as_ref().and_then(|output| {
+            self.config
+                .workspaces()?
+                .into_iter()
+                .find(|wsc| wsc.output.as_ref() == Some(output))
+        });
+        let output_config = output_config.as_ref();
+        let layout = output_config
+            .and_then(|wsc| wsc.layout)
+            .unwrap_or_else(|| self.state.layout_manager.new_layout());
         let mut new_workspace = Workspace::new(
-            screen.wsid,
+            output_config.and_then(|wsc| wsc.id).or(screen.wsid),
             screen.bbox,
-            self.state.layout_manager.new_layout(),
-            screen.max_window_width.or(self.state.max_window_width),
+            layout,
+            screen
+                .max_window_width
+                .or_else(|| output_config.and_then(|wsc| wsc.max_window_width))
+                .or(self.state.max_window_width),
         );
+        new_workspace.output.clone_from(&screen.output);
         if new_workspace.id.is_none() {
             new_workspace.id = Some(
                 self.state
@@ -27,18 +50,32 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             );
         }
         if new_workspace.id.unwrap_or(0) as usize >= tag_len {
-            dbg!("Workspace ID needs to be less than or equal to the number of tags available.");
+            log::warn!(
+                "Workspace ID {:?} needs to be less than the number of tags ({}).",
+                new_workspace.id,
+                tag_len
+            );
         }
         new_workspace.load_config(&self.config);
+        if self.state.tag_mode == TagMode::PerMonitor {
+            new_workspace.tag_offset = self.create_monitor_tags();
+        }
 
         //make sure are enough tags for this new screen
-        let next_id = if tag_len > tag_index {
-            tag_index + 1
-        } else {
+        let owned_tags = self.state.workspace_tags(&new_workspace);
+        let default_tag = output_config
+            .and_then(|wsc| wsc.default_tag.as_ref())
+            .and_then(|label| self.tag_by_label(label, &owned_tags))
+            .filter(|tag| !self.is_shown(*tag));
+        let free_tag =
+            default_tag.or_else(|| owned_tags.into_iter().find(|tag| !self.is_shown(*tag)));
+        let next_id = match free_tag {
+            Some(tag) => tag,
             // add a new tag for the workspace
-            self.state
+            None => self
+                .state
                 .tags
-                .add_new_unlabeled(self.state.layout_manager.new_layout())
+                .add_new_unlabeled(self.state.layout_manager.new_layout()),
         };
 
         self.state.focus_workspace(&new_workspace);
@@ -46,15 +83,54 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         new_workspace.show_tag(&next_id);
         self.state.workspaces.push(new_workspace.clone());
         self.state.workspaces.sort_by(|a, b| a.id.cmp(&b.id));
-        self.state.screens.push(screen);
         self.state.focus_workspace(&new_workspace);
-        false
+    }
+
+    /// Finds the first block of tags not owned by a workspace, creating its tags if needed.
+    /// Returns the offset of the block.
+    fn create_monitor_tags(&mut self) -> usize {
+        let per_monitor = self.state.tags_per_monitor;
+        if per_monitor == 0 {
+            return 0;
+        }
+        // one of the blocks up to the number of workspaces is free
+        let offset = (0..=self.state.workspaces.len())
+            .map(|block| block * per_monitor)
+            .find(|offset| {
+                !self
+                    .state
+                    .workspaces
+                    .iter()
+                    .any(|ws| ws.tag_offset == *offset)
+            })
+            .unwrap_or_default();
+        while self.state.tags.len_normal() < offset + per_monitor {
+            let index = self.state.tags.len_normal() % per_monitor;
+            let label = self.state.tags.normal()[index].label.clone();
+            let layout = self.state.layout_manager.new_layout();
+            self.state.tags.add_new(&label, layout);
+        }
+        offset
+    }
+
+    fn tag_by_label(&self, label: &str, tags: &[TagId]) -> Option<TagId> {
+        self.state
+            .tags
+            .normal()
+            .iter()
+            .find(|tag| tag.label == label && tags.contains(&tag.id))
+            .map(|tag| tag.id)
+    }
+
+    fn is_shown(&self, tag: TagId) -> bool {
+        self.state.workspaces.iter().any(|ws| ws.has_tag(&tag))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{Gutter, Margins, Side};
     use crate::Manager;
 
     #[test]
@@ -103,4 +179,28 @@ mod tests {
         assert!(manager.state.workspaces[2].has_tag(&3));
         assert!(manager.state.workspaces[3].has_tag(&4));
     }
+
+    #[test]
+    fn screens_should_get_the_settings_of_their_output() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.config.workspaces = vec![crate::config::Workspace {
+            output: Some("DP-2".to_string()),
+            id: Some(4),
+            default_tag: Some("2".to_string()),
+            margin: Some(Margins::new(5)),
+            gutters: Some(vec![Gutter::new(Side::Top, 20, None)]),
+            ..crate::config::Workspace::default()
+        }];
+        let screen = Screen {
+            output: Some("DP-2".to_string()),
+            ..Screen::default()
+        };
+        manager.screen_create_handler(screen);
+        let workspace = &manager.state.workspaces[0];
+        assert_eq!(workspace.id, Some(4));
+        assert_eq!(workspace.output.as_deref(), Some("DP-2"));
+        assert!(workspace.has_tag(&2));
+        assert_eq!(workspace.margin, Margins::new(5));
+        assert_eq!(workspace.gutters, vec![Gutter::new(Side::Top, 20, None)]);
+    }
 }
@@ -0,0 +1,187 @@
+use super::{Manager, Screen};
+use crate::config::Config;
+use crate::display_servers::DisplayServer;
+
+impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
+    /// Applies the screens found after monitors were plugged, unplugged or reconfigured. Screens
+    /// are matched to the previous ones by output name, or in order for screens without one, and
+    /// their workspaces are scaled to the new size; workspaces are created for new screens and
+    /// the windows of removed ones are moved to the focused workspace.
+    ///
+    /// Returns `true` if changes need to be rendered.
+    pub fn screens_changed_handler(&mut self, screens: Vec<Screen>) -> bool {
+        let unchanged = screens.len() == self.state.screens.len()
+            && screens
+                .iter()
+                .zip(&self.state.screens)
+                .all(|(new, old)| new.bbox == old.bbox && new.output == old.output);
+        if unchanged || screens.is_empty() {
+            return false;
+        }
+
+        // Match the screens to the previous ones, each workspace stays on the screen it was on,
+        // along with the workspaces split from it.
+        let old_screens = std::mem::take(&mut self.state.screens);
+        let mut unclaimed: Vec<Option<Screen>> = screens.into_iter().map(Some).collect();
+        let mut claims: Vec<Option<Screen>> = vec![None; old_screens.len()];
+        for (old, claim) in old_screens.iter().zip(&mut claims) {
+            if let Some(output) = &old.output {
+                *claim = unclaimed
+                    .iter_mut()
+                    .find(|s| matches!(s, Some(s) if s.output.as_ref() == Some(output)))
+                    .and_then(Option::take);
+            }
+        }
+        for (index, (old, claim)) in old_screens.iter().zip(&mut claims).enumerate() {
+            if old.output.is_none() {
+                *claim = unclaimed.get_mut(index).and_then(Option::take);
+            }
+        }
+        self.state.screens = claims.iter().flatten().cloned().collect();
+
+        // Resize the workspaces of the monitors that are still there, and remove the others.
+        let mut kept = vec![];
+        let mut removed = vec![];
+        let mut new_indexes = vec![];
+        let workspaces = std::mem::take(&mut self.state.workspaces);
+        for mut ws in workspaces {
+            let bbox = ws.bbox();
+            let screen = old_screens
+                .iter()
+                .zip(&claims)
+                .find(|(old, _)| old.contains_bbox(&bbox));
+            if let Some((old, Some(new))) = screen {
+                ws.set_bbox(bbox.moved_within(old.bbox, new.bbox));
+                new_indexes.push(Some(kept.len()));
+                kept.push(ws);
+            } else {
+                new_indexes.push(None);
+                removed.push(ws);
+            }
+        }
+        self.state.workspaces = kept;
+        let history = &mut self.state.focus_manager.workspace_history;
+        *history = history
+            .iter()
+            .filter_map(|index| new_indexes.get(*index).copied().flatten())
+            .collect();
+        if history.is_empty() && !self.state.workspaces.is_empty() {
+            history.push_front(0);
+        }
+
+        // Create workspaces for new monitors.
+        for screen in unclaimed.into_iter().flatten() {
+            self.screen_create_handler(screen);
+        }
+
+        // Move the windows of unplugged monitors.
+        self.state.adopt_windows(&removed);
+
+        self.update_workspace_avoid_list();
+        self.state.update_static();
+        self.state.update_current_tags();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BBox, SplitDirection, TagMode, Window, WindowHandle};
+    use crate::Command;
+
+    fn screen(x: i32, width: i32) -> Screen {
+        Screen::new(BBox {
+            x,
+            y: 0,
+            width,
+            height: 1080,
+        })
+    }
+
+    #[test]
+    fn plugging_a_monitor_should_create_a_workspace() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(screen(0, 1920));
+        assert!(manager.screens_changed_handler(vec![screen(0, 1920), screen(1920, 1280)]));
+        assert_eq!(manager.state.workspaces.len(), 2);
+        assert!(manager.state.workspaces[1].has_tag(&2));
+        assert_eq!(manager.state.workspaces[1].xyhw.x(), 1920);
+    }
+
+    #[test]
+    fn unplugging_a_monitor_should_move_its_windows() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(screen(0, 1920));
+        manager.screen_create_handler(screen(1920, 1280));
+        let mut window = Window::new(WindowHandle::MockHandle(1), None, None);
+        window.tag(&2);
+        manager.state.windows.push(window);
+
+        assert!(manager.screens_changed_handler(vec![screen(0, 2560)]));
+        assert_eq!(manager.state.workspaces.len(), 1);
+        assert_eq!(manager.state.workspaces[0].xyhw.w(), 2560);
+        assert!(manager.state.windows[0].has_tag(&1));
+    }
+
+    #[test]
+    fn unplugging_a_monitor_should_move_the_windows_of_all_its_tags() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.state.tag_mode = TagMode::PerMonitor;
+        manager.screen_create_handler(screen(0, 1920));
+        manager.screen_create_handler(screen(1920, 1280));
+        // the second monitor shows tag 3, the window is on its hidden tag 4
+        let mut window = Window::new(WindowHandle::MockHandle(1), None, None);
+        window.tag(&4);
+        manager.state.windows.push(window);
+
+        assert!(manager.screens_changed_handler(vec![screen(0, 1920)]));
+        assert!(manager.state.windows[0].has_tag(&1));
+        assert_eq!(manager.state.tag_owner(1), Some(0));
+    }
+
+    #[test]
+    fn unchanged_screens_should_not_update_anything() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(screen(0, 1920));
+        assert!(!manager.screens_changed_handler(vec![screen(0, 1920)]));
+    }
+
+    #[test]
+    fn workspaces_should_follow_their_output_when_monitors_are_reordered() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        let mut left = screen(0, 1920);
+        left.output = Some("DP-1".to_string());
+        let mut right = screen(1920, 1280);
+        right.output = Some("DP-2".to_string());
+        manager.screen_create_handler(left.clone());
+        manager.screen_create_handler(right.clone());
+
+        left.bbox.x = 1280;
+        right.bbox.x = 0;
+        assert!(manager.screens_changed_handler(vec![right, left]));
+        let dp2 = &manager.state.workspaces[1];
+        assert_eq!(dp2.output.as_deref(), Some("DP-2"));
+        assert_eq!(dp2.xyhw.x(), 0);
+        assert!(dp2.has_tag(&2));
+    }
+
+    #[test]
+    fn split_screens_should_stay_split() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(screen(0, 1920));
+        let split = Command::SplitWorkspace(SplitDirection::Horizontal, 50);
+        assert!(manager.command_handler(&split));
+        assert_eq!(manager.state.screens.len(), 1);
+
+        assert!(!manager.screens_changed_handler(vec![screen(0, 1920)]));
+        assert_eq!(manager.state.workspaces.len(), 2);
+
+        // both parts follow the screen when it is resized
+        assert!(manager.screens_changed_handler(vec![screen(0, 1280)]));
+        assert_eq!(manager.state.workspaces.len(), 2);
+        assert_eq!(manager.state.workspaces[0].xyhw.w(), 640);
+        assert_eq!(manager.state.workspaces[1].xyhw.x(), 640);
+        assert_eq!(manager.state.workspaces[1].xyhw.w(), 640);
+    }
+}
@@ -3,10 +3,12 @@ use crate::config::{Config, ScratchPad};
 use crate::display_action::DisplayAction;
 use crate::display_servers::DisplayServer;
 use crate::layouts::Layout;
-use crate::models::{Size, WindowHandle, WindowState, Xyhw, XyhwBuilder};
+use crate::models::FocusBehaviour;
+use crate::models::{
+    FloatingPlacement, Size, SnapPosition, WindowHandle, WindowState, Xyhw, XyhwBuilder,
+};
 use crate::state::State;
 use crate::utils::helpers;
-use crate::{child_process::exec_shell, models::FocusBehaviour};
 use std::env;
 use std::str::FromStr;
 
@@ -32,9 +34,14 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             &mut on_same_tag,
         );
         window.load_config(&self.config);
+        if let Some(placement) = self.config.floating_placement() {
+            place_floating_window(&self.state, &mut window, placement, (x, y));
+        }
         insert_window(&mut self.state, &mut window, layout);
 
-        let follow_mouse = self.state.focus_manager.focus_new_windows
+        let steal_focus = self.state.focus_manager.focus_new_windows
+            && self.state.may_steal_focus(&window, window.user_time);
+        let follow_mouse = steal_focus
             && self.state.focus_manager.behaviour == FocusBehaviour::Sloppy
             && on_same_tag;
         //let the DS know we are managing this window
@@ -50,12 +57,10 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         // Tell the WM the new display order of the windows.
         self.state.sort_windows();
 
-        if (self.state.focus_manager.focus_new_windows || is_first) && on_same_tag {
+        if (steal_focus || is_first) && on_same_tag {
             self.state.focus_window(&window.handle);
-        }
-
-        if let Some(cmd) = &self.config.on_new_window_cmd() {
-            exec_shell(cmd, &mut self.children);
+        } else if self.state.focus_manager.focus_new_windows && !steal_focus {
+            self.state.mark_urgent(window.handle);
         }
 
         true
@@ -70,7 +75,12 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             .focus_manager
             .tags_last_window
             .retain(|_, h| h != handle);
+        let managed = self.state.windows.iter().any(|w| &w.handle == handle);
         self.state.windows.retain(|w| &w.handle != handle);
+        if managed {
+            // Keep the stacking order known by the display server up to date.
+            self.state.sort_windows();
+        }
 
         //make sure the workspaces do not draw on the docks
         self.update_workspace_avoid_list();
@@ -96,7 +106,9 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     pub fn window_changed_handler(&mut self, change: WindowChange) -> bool {
         let mut changed = false;
         let mut fullscreen_changed = false;
+        let mut stacking_changed = false;
         let strut_changed = change.strut.is_some();
+        let placed = self.config.floating_placement().is_some();
         let windows = self.state.windows.clone();
         if let Some(window) = self
             .state
@@ -107,6 +119,9 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             if let Some(ref states) = change.states {
                 let change_contains = states.contains(&WindowState::Fullscreen);
                 fullscreen_changed = change_contains || window.is_fullscreen();
+                stacking_changed = [WindowState::Above, WindowState::Below]
+                    .iter()
+                    .any(|s| states.contains(s) != window.has_state(s));
             }
             let is_floating_change = change.floating.is_some();
             log::debug!("WINDOW CHANGED {:?} {:?}", &window, change);
@@ -124,6 +139,8 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
                     let transient = window.transient;
                     match find_transient_parent(&windows, transient) {
                         Some(parent) => set_relative_floating(window, ws, parent.exact_xyhw()),
+                        // Keep the spot the floating placement found for it.
+                        None if placed && transient.is_none() => resize_floating(window, ws),
                         None => set_relative_floating(window, ws, ws.xyhw),
                     }
                 }
@@ -135,7 +152,9 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
                 // infinite loop. Just be patient a rerender will occur.
             }
         }
-        if fullscreen_changed {
+        if stacking_changed {
+            self.state.sort_windows();
+        } else if fullscreen_changed {
             // Reorder windows.
             let act = DisplayAction::SetWindowOrder(self.state.windows.clone());
             self.state.actions.push_back(act);
@@ -158,6 +177,11 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
                 avoid.push(to_avoid);
             });
         for ws in &mut self.state.workspaces {
+            if !ws.avoids_struts() {
+                ws.avoid = vec![];
+                ws.update_avoided_areas();
+                continue;
+            }
             let struts = avoid
                 .clone()
                 .into_iter()
@@ -366,6 +390,129 @@ fn set_relative_floating(window: &mut Window, ws: &Workspace, outer: Xyhw) {
     window.set_floating_exact(xyhw);
 }
 
+/// Resizes a floating window to the size it asks for, without moving it.
+fn resize_floating(window: &mut Window, ws: &Workspace) {
+    let mut xyhw = window.exact_xyhw();
+    if let Some(requested) = window.requested.filter(|r| r.w() > 0 && r.h() > 0) {
+        xyhw.set_w(requested.w());
+        xyhw.set_h(requested.h());
+    }
+    window.set_floating(true);
+    window.normal = ws.xyhw;
+    window.set_floating_exact(xyhw.clamped_to(&ws.xyhw_avoided()));
+}
+
+/// Moves a new floating window without a parent according to the configured placement.
+/// Dialogs and transients with a parent keep their position relative to it.
+fn place_floating_window(
+    state: &State,
+    window: &mut Window,
+    placement: FloatingPlacement,
+    cursor: (i32, i32),
+) -> Option<()> {
+    if !window.floating()
+        || window.transient.is_some()
+        || is_scratchpad(state, window)
+        || !matches!(window.r#type, WindowType::Normal | WindowType::Dialog)
+    {
+        return None;
+    }
+    let ws = state
+        .workspaces
+        .iter()
+        .find(|ws| ws.is_displaying(window))?;
+    let area = ws.xyhw_avoided();
+    let size = match window.requested {
+        Some(requested) if requested.w() > 0 && requested.h() > 0 => requested,
+        _ => ws.center_halfed(),
+    };
+    let centered = SnapPosition::Center.snap(&size, &area);
+    let others: Vec<Xyhw> = state
+        .windows
+        .iter()
+        .filter(|w| w.floating() && !w.is_unmanaged() && ws.is_displaying(w))
+        .map(Window::exact_xyhw)
+        .collect();
+    let xyhw = match placement {
+        FloatingPlacement::Center => centered,
+        FloatingPlacement::UnderCursor => offset_xyhw(
+            &centered,
+            cursor.0 - size.w() / 2 - centered.x(),
+            cursor.1 - size.h() / 2 - centered.y(),
+        ),
+        FloatingPlacement::Smart => smart_xyhw(&centered, &area, &others),
+        FloatingPlacement::Cascade => cascade_xyhw(&centered, &area, &others),
+    };
+    window.normal = ws.xyhw;
+    window.set_floating_exact(xyhw.clamped_to(&area));
+    Some(())
+}
+
+fn offset_xyhw(xyhw: &Xyhw, dx: i32, dy: i32) -> Xyhw {
+    XyhwBuilder {
+        x: xyhw.x() + dx,
+        y: xyhw.y() + dy,
+        h: xyhw.h(),
+        w: xyhw.w(),
+        ..XyhwBuilder::default()
+    }
+    .into()
+}
+
+/// Try the center first, then a grid over the area, and keep the spot that overlaps the least
+/// with the other floating windows.
+fn smart_xyhw(centered: &Xyhw, area: &Xyhw, others: &[Xyhw]) -> Xyhw {
+    const STEPS: i32 = 8;
+    let overlap = |xyhw: &Xyhw| -> u64 { others.iter().map(|o| o.overlap(xyhw)).sum() };
+    let free_w = (area.w() - centered.w()).max(0);
+    let free_h = (area.h() - centered.h()).max(0);
+    let mut best = *centered;
+    let mut best_overlap = overlap(&best);
+    for row in 0..=STEPS {
+        for column in 0..=STEPS {
+            if best_overlap == 0 {
+                return best;
+            }
+            let candidate = offset_xyhw(
+                centered,
+                area.x() + free_w * column / STEPS - centered.x(),
+                area.y() + free_h * row / STEPS - centered.y(),
+            );
+            let candidate_overlap = overlap(&candidate);
+            if candidate_overlap < best_overlap {
+                best = candidate;
+                best_overlap = candidate_overlap;
+            }
+        }
+    }
+    best
+}
+
+/// Step down and to the right from the center until a spot is found where no other floating
+/// window starts, wrapping to the top left corner of the area when running out of room.
+fn cascade_xyhw(centered: &Xyhw, area: &Xyhw, others: &[Xyhw]) -> Xyhw {
+    const STEP: i32 = 32;
+    let fits = |xyhw: &Xyhw| {
+        xyhw.x() + xyhw.w() <= area.x() + area.w() && xyhw.y() + xyhw.h() <= area.y() + area.h()
+    };
+    let taken = |xyhw: &Xyhw| {
+        others
+            .iter()
+            .any(|o| (o.x() - xyhw.x()).abs() < STEP / 2 && (o.y() - xyhw.y()).abs() < STEP / 2)
+    };
+    let top_left = SnapPosition::TopLeft.snap(centered, area);
+    for start in &[*centered, top_left] {
+        let mut candidate = *start;
+        while fits(&candidate) {
+            if !taken(&candidate) {
+                return candidate;
+            }
+            candidate = offset_xyhw(&candidate, STEP, STEP);
+        }
+    }
+    *centered
+}
+
 fn is_scratchpad(state: &State, window: &Window) -> bool {
     state
         .active_scratchpads
@@ -450,3 +597,107 @@ fn sane_dimension(config_value: Option<Size>, default_ratio: f32, max_pixel: i32
         _ => Size::Ratio(default_ratio).into_absolute(max_pixel),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TestConfig;
+    use crate::display_servers::MockDisplayServer;
+    use crate::models::{BBox, Screen};
+
+    fn manager_with_placement(
+        placement: FloatingPlacement,
+    ) -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.config.floating_placement = Some(placement);
+        manager.screen_create_handler(Screen::new(BBox {
+            x: 0,
+            y: 0,
+            width: 1000,
+            height: 800,
+        }));
+        manager
+    }
+
+    fn create_dialog(manager: &mut Manager<TestConfig, MockDisplayServer>, handle: i32) -> Xyhw {
+        let mut window = Window::new(WindowHandle::MockHandle(handle), None, None);
+        window.r#type = WindowType::Dialog;
+        window.requested = Some(
+            XyhwBuilder {
+                w: 200,
+                h: 100,
+                ..XyhwBuilder::default()
+            }
+            .into(),
+        );
+        manager.window_created_handler(window, -1, -1);
+        manager
+            .state
+            .windows
+            .iter()
+            .find(|w| w.handle == WindowHandle::MockHandle(handle))
+            .unwrap()
+            .exact_xyhw()
+    }
+
+    #[test]
+    fn cascade_placement_should_not_stack_dialogs_on_each_other() {
+        let mut manager = manager_with_placement(FloatingPlacement::Cascade);
+        let first = create_dialog(&mut manager, 1);
+        let second = create_dialog(&mut manager, 2);
+        assert_eq!((first.x(), first.y()), (400, 350));
+        assert_eq!((second.x(), second.y()), (432, 382));
+    }
+
+    #[test]
+    fn placed_dialogs_should_stay_in_place_when_resized() {
+        let mut manager = manager_with_placement(FloatingPlacement::Cascade);
+        create_dialog(&mut manager, 1);
+        create_dialog(&mut manager, 2);
+        let mut change = WindowChange::new(WindowHandle::MockHandle(2));
+        change.requested = Some(
+            XyhwBuilder {
+                w: 300,
+                h: 150,
+                ..XyhwBuilder::default()
+            }
+            .into(),
+        );
+        manager.window_changed_handler(change);
+        let dialog = manager
+            .state
+            .windows
+            .iter()
+            .find(|w| w.handle == WindowHandle::MockHandle(2));
+        let xyhw = dialog.unwrap().exact_xyhw();
+        assert_eq!((xyhw.x(), xyhw.y()), (432, 382));
+        assert_eq!((xyhw.w(), xyhw.h()), (300, 150));
+    }
+
+    #[test]
+    fn smart_placement_should_avoid_other_floating_windows() {
+        let mut manager = manager_with_placement(FloatingPlacement::Smart);
+        let first = create_dialog(&mut manager, 1);
+        let second = create_dialog(&mut manager, 2);
+        assert_eq!((first.x(), first.y()), (400, 350));
+        assert_eq!(first.overlap(&second), 0);
+    }
+
+    #[test]
+    fn above_and_below_states_should_restack_windows() {
+        let mut manager = manager_with_placement(FloatingPlacement::Center);
+        for handle in 1..=3 {
+            let window = Window::new(WindowHandle::MockHandle(handle), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+        let mut change = WindowChange::new(WindowHandle::MockHandle(3));
+        change.states = Some(vec![WindowState::Below]);
+        manager.window_changed_handler(change);
+        let mut change = WindowChange::new(WindowHandle::MockHandle(1));
+        change.states = Some(vec![WindowState::Above]);
+        manager.window_changed_handler(change);
+        let order: Vec<WindowHandle> = manager.state.windows.iter().map(|w| w.handle).collect();
+        assert_eq!(order.first(), Some(&WindowHandle::MockHandle(1)));
+        assert_eq!(order.last(), Some(&WindowHandle::MockHandle(3)));
+    }
+}
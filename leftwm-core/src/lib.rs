@@ -22,9 +22,12 @@ mod display_event;
 pub mod display_servers;
 pub mod errors;
 mod event_loop;
+mod events;
 mod handlers;
 pub mod layouts;
 pub mod models;
+mod query;
+pub mod simulation;
 pub mod state;
 pub mod utils;
 
@@ -32,18 +35,22 @@ use utils::xkeysym_lookup::Button;
 use utils::xkeysym_lookup::ModMask;
 use utils::xkeysym_lookup::XKeysym;
 
-pub use command::Command;
+pub use command::{resolve_alias, Command, CommandError};
 pub use config::{Config, Keybind};
 pub use display_action::DisplayAction;
 pub use display_event::DisplayEvent;
 pub use display_servers::xlib_display_server::XWrap;
 pub use display_servers::DisplayServer;
 pub use display_servers::XlibDisplayServer;
+pub use events::{Event, EventKind, EventTracker};
 pub use models::Manager;
 pub use models::Mode;
 pub use models::Window;
 pub use models::Workspace;
+pub use query::Query;
 pub use state::State;
 pub use utils::child_process;
 pub use utils::command_pipe::CommandPipe;
+pub use utils::command_socket::CommandSocket;
+pub use utils::config_watcher::ConfigWatcher;
 pub use utils::state_socket::StateSocket;
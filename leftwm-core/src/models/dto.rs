@@ -1,4 +1,5 @@
 use crate::layouts::Layout;
+use crate::models::{Tag, TagId, Window, WindowHandle, WindowState, WindowType, Workspace, Xyhw};
 use crate::state::State;
 use serde::{Deserialize, Serialize};
 
@@ -157,3 +158,163 @@ impl From<&State> for ManagerState {
         }
     }
 }
+
+/// Position and size of a window or workspace.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Geometry {
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+}
+
+impl From<Xyhw> for Geometry {
+    fn from(xyhw: Xyhw) -> Self {
+        Self {
+            x: xyhw.x(),
+            y: xyhw.y(),
+            w: xyhw.w(),
+            h: xyhw.h(),
+        }
+    }
+}
+
+/// A window, as answered to the `GetWindows` query.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WindowInfo {
+    /// The X window id.
+    pub handle: u64,
+    pub class: Option<String>,
+    pub title: Option<String>,
+    pub pid: Option<u32>,
+    pub r#type: WindowType,
+    /// Labels of the tags of the window.
+    pub tags: Vec<String>,
+    pub floating: bool,
+    pub visible: bool,
+    pub focused: bool,
+    pub geometry: Geometry,
+    pub states: Vec<WindowState>,
+}
+
+impl WindowInfo {
+    #[must_use]
+    pub fn new(state: &State, window: &Window) -> Self {
+        let focused = state.focus_manager.window(&state.windows);
+        Self {
+            handle: window_id(window.handle),
+            class: window.res_class.clone(),
+            title: window.name.clone(),
+            pid: window.pid,
+            r#type: window.r#type.clone(),
+            tags: tag_labels(state, &window.tags),
+            floating: window.floating(),
+            visible: window.visible(),
+            focused: focused.map(|w| w.handle) == Some(window.handle),
+            geometry: window.calculated_xyhw().into(),
+            states: window.states(),
+        }
+    }
+}
+
+/// A workspace, as answered to the `GetWorkspaces` query.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WorkspaceInfo {
+    pub index: usize,
+    pub id: Option<i32>,
+    /// Name of the `RandR` output displaying the workspace.
+    pub output: Option<String>,
+    pub layout: Layout,
+    /// Labels of the tags shown by the workspace.
+    pub tags: Vec<String>,
+    pub focused: bool,
+    pub geometry: Geometry,
+}
+
+impl WorkspaceInfo {
+    #[must_use]
+    pub fn new(state: &State, index: usize, workspace: &Workspace) -> Self {
+        Self {
+            index,
+            id: workspace.id,
+            output: workspace.output.clone(),
+            layout: workspace.layout,
+            tags: tag_labels(state, &workspace.tags),
+            focused: state.focus_manager.workspace_history.front() == Some(&index),
+            geometry: workspace.xyhw.into(),
+        }
+    }
+}
+
+/// A tag, as answered to the `GetTags` query.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TagInfo {
+    pub id: TagId,
+    pub label: String,
+    pub layout: Layout,
+    /// Whether a workspace shows the tag.
+    pub visible: bool,
+    pub focused: bool,
+    /// Number of windows on the tag.
+    pub windows: usize,
+}
+
+impl TagInfo {
+    #[must_use]
+    pub fn new(state: &State, tag: &Tag) -> Self {
+        Self {
+            id: tag.id,
+            label: tag.label.clone(),
+            layout: tag.layout,
+            visible: state.workspaces.iter().any(|ws| ws.has_tag(&tag.id)),
+            focused: state.focus_manager.tag(0) == Some(tag.id),
+            windows: state.windows.iter().filter(|w| w.has_tag(&tag.id)).count(),
+        }
+    }
+}
+
+/// What has the focus, as answered to the `GetFocused` query.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FocusedInfo {
+    pub window: Option<WindowInfo>,
+    pub workspace: Option<WorkspaceInfo>,
+    pub tag: Option<TagInfo>,
+}
+
+impl From<&State> for FocusedInfo {
+    fn from(state: &State) -> Self {
+        let workspace = state
+            .focus_manager
+            .workspace_history
+            .front()
+            .and_then(|index| Some((*index, state.workspaces.get(*index)?)));
+        Self {
+            window: state
+                .focus_manager
+                .window(&state.windows)
+                .map(|window| WindowInfo::new(state, window)),
+            workspace: workspace.map(|(index, ws)| WorkspaceInfo::new(state, index, ws)),
+            tag: state
+                .focus_manager
+                .tag(0)
+                .and_then(|tag| state.tags.get(tag))
+                .map(|tag| TagInfo::new(state, tag)),
+        }
+    }
+}
+
+/// The id of a window, as shown to clients.
+#[must_use]
+pub fn window_id(handle: WindowHandle) -> u64 {
+    match handle {
+        WindowHandle::MockHandle(h) => h as u64,
+        WindowHandle::XlibHandle(h) => h,
+    }
+}
+
+fn tag_labels(state: &State, tags: &[TagId]) -> Vec<String> {
+    tags.iter()
+        .filter_map(|tag| state.tags.get(*tag))
+        .map(|tag| tag.label.clone())
+        .collect()
+}
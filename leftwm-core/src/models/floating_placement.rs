@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// Where new floating windows without a parent are placed on their workspace.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum FloatingPlacement {
+    /// Centered on the workspace.
+    #[serde(alias = "center")]
+    Center,
+    /// Centered on the mouse cursor.
+    #[serde(alias = "under_cursor")]
+    UnderCursor,
+    /// Wherever it overlaps the least with the other floating windows.
+    #[serde(alias = "smart")]
+    Smart,
+    /// Slightly below and to the right of the last floating window.
+    #[serde(alias = "cascade")]
+    Cascade,
+}
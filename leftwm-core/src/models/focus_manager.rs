@@ -19,10 +19,27 @@ impl Default for FocusBehaviour {
     }
 }
 
+/// What happens when a window asks for the focus, by being created or with an activation request.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum FocusStealingPolicy {
+    /// Switch to the tag of the window and focus it.
+    #[default]
+    #[serde(alias = "focus")]
+    Focus,
+    /// Only mark the window as urgent.
+    #[serde(alias = "urgent")]
+    Urgent,
+    /// Focus the window if the request follows the last user input, otherwise mark it as urgent.
+    #[serde(alias = "smart")]
+    Smart,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FocusManager {
     pub behaviour: FocusBehaviour,
     pub focus_new_windows: bool,
+    pub focus_stealing_policy: FocusStealingPolicy,
+    pub focus_stealing_overrides: HashMap<String, FocusStealingPolicy>,
     pub workspace_history: VecDeque<usize>,
     pub window_history: VecDeque<MaybeWindowHandle>,
     pub tag_history: VecDeque<TagId>,
@@ -34,6 +51,8 @@ impl FocusManager {
         Self {
             behaviour: config.focus_behaviour(),
             focus_new_windows: config.focus_new_windows(),
+            focus_stealing_policy: config.focus_stealing_policy(),
+            focus_stealing_overrides: config.focus_stealing_overrides(),
             workspace_history: Default::default(),
             window_history: Default::default(),
             tag_history: Default::default(),
@@ -41,6 +60,17 @@ impl FocusManager {
         }
     }
 
+    /// Return the focus stealing policy of a window, which can be overridden by its class.
+    #[must_use]
+    pub fn stealing_policy(&self, window: &Window) -> FocusStealingPolicy {
+        window
+            .res_class
+            .as_ref()
+            .and_then(|class| self.focus_stealing_overrides.get(class))
+            .copied()
+            .unwrap_or(self.focus_stealing_policy)
+    }
+
     /// Return the currently focused workspace.
     #[must_use]
     pub fn workspace<'a, 'b>(&self, workspaces: &'a [Workspace]) -> Option<&'b Workspace>
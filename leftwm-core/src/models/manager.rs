@@ -58,6 +58,13 @@ where
 #[cfg(test)]
 impl Manager<crate::config::TestConfig, crate::display_servers::MockDisplayServer> {
     pub fn new_test(tags: Vec<String>) -> Self {
-        Self::new(crate::config::TestConfig { tags })
+        Self::new(crate::config::TestConfig {
+            tags,
+            keybinds: vec![],
+            floating_placement: None,
+            workspaces: vec![],
+            tag_mode: crate::config::TagMode::Shared,
+            aliases: std::collections::HashMap::new(),
+        })
     }
 }
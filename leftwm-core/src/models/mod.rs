@@ -1,5 +1,6 @@
 //! Objects (such as windows) used to develop `LeftWM`.
 mod dock_area;
+mod floating_placement;
 mod focus_manager;
 mod gutter;
 mod layout_manager;
@@ -8,6 +9,9 @@ mod margins;
 mod mode;
 mod screen;
 mod size;
+mod size_hints;
+mod snap_position;
+mod split_direction;
 mod tag;
 mod window;
 mod window_change;
@@ -21,8 +25,10 @@ pub mod dto;
 use crate::layouts;
 
 pub use dock_area::DockArea;
+pub use floating_placement::FloatingPlacement;
 pub use focus_manager::FocusBehaviour;
 pub use focus_manager::FocusManager;
+pub use focus_manager::FocusStealingPolicy;
 pub use gutter::Gutter;
 pub use gutter::Side;
 pub use layout_manager::LayoutManager;
@@ -32,6 +38,9 @@ pub use margins::Margins;
 pub use mode::Mode;
 pub use screen::{BBox, Screen};
 pub use size::Size;
+pub use size_hints::SizeHints;
+pub use snap_position::SnapPosition;
+pub use split_direction::SplitDirection;
 pub use window::Window;
 pub use window::WindowHandle;
 pub use window_change::WindowChange;
@@ -43,6 +52,7 @@ pub use xyhw::XyhwBuilder;
 pub use xyhw_change::XyhwChange;
 
 pub use tag::Tag;
+pub use tag::TagMode;
 pub use tag::Tags;
 
 pub type TagId = usize;
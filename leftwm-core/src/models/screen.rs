@@ -11,10 +11,13 @@ pub struct Screen {
     pub bbox: BBox,
     pub wsid: Option<i32>,
     pub max_window_width: Option<Size>,
+    /// Name of the `RandR` output displaying the screen.
+    #[serde(default)]
+    pub output: Option<String>,
 }
 
 /// Screen Bounding Box
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct BBox {
     pub x: i32,
     pub y: i32,
@@ -22,6 +25,22 @@ pub struct BBox {
     pub height: i32,
 }
 
+impl BBox {
+    /// This box, placed and scaled in `to` like it is in `from`.
+    #[must_use]
+    pub fn moved_within(self, from: Self, to: Self) -> Self {
+        let scale_x = |x: i32| to.x + (x - from.x) * to.width / from.width;
+        let scale_y = |y: i32| to.y + (y - from.y) * to.height / from.height;
+        let (x, y) = (scale_x(self.x), scale_y(self.y));
+        Self {
+            x,
+            y,
+            width: scale_x(self.x + self.width) - x,
+            height: scale_y(self.y + self.height) - y,
+        }
+    }
+}
+
 impl Screen {
     #[must_use]
     pub const fn new(bbox: BBox) -> Self {
@@ -30,6 +49,7 @@ impl Screen {
             bbox,
             wsid: None,
             max_window_width: None,
+            output: None,
         }
     }
 
@@ -41,6 +61,16 @@ impl Screen {
         (bbox.x <= x && x <= max_x) && (bbox.y <= y && y <= max_y)
     }
 
+    /// Whether `bbox` is entirely on the screen, like the workspaces of a split screen.
+    #[must_use]
+    pub const fn contains_bbox(&self, bbox: &BBox) -> bool {
+        let own = &self.bbox;
+        own.x <= bbox.x
+            && own.y <= bbox.y
+            && bbox.x + bbox.width <= own.x + own.width
+            && bbox.y + bbox.height <= own.y + own.height
+    }
+
     #[must_use]
     pub const fn contains_dock_area(&self, dock_area: DockArea, screens_area: (i32, i32)) -> bool {
         if dock_area.top > 0 {
@@ -72,6 +102,7 @@ impl From<&Workspace> for Screen {
             },
             wsid: wsc.id,
             max_window_width: wsc.max_window_width,
+            output: wsc.output.clone(),
         }
     }
 }
@@ -88,6 +119,7 @@ impl From<&xlib::XWindowAttributes> for Screen {
             },
             wsid: None,
             max_window_width: None,
+            output: None,
         }
     }
 }
@@ -104,6 +136,7 @@ impl From<&x11_dl::xinerama::XineramaScreenInfo> for Screen {
             },
             wsid: None,
             max_window_width: None,
+            output: None,
         }
     }
 }
@@ -120,6 +153,7 @@ impl Default for Screen {
             },
             wsid: None,
             max_window_width: None,
+            output: None,
         }
     }
 }
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+/// The ICCCM `WM_NORMAL_HINTS` of a window that tiled layouts can honour.
+///
+/// A value of `0` means the hint was not set by the window.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct SizeHints {
+    pub base_w: i32,
+    pub base_h: i32,
+    pub inc_w: i32,
+    pub inc_h: i32,
+    pub min_w: i32,
+    pub min_h: i32,
+    pub max_w: i32,
+    pub max_h: i32,
+    /// Smallest allowed `width / height`.
+    pub min_aspect: f32,
+    /// Largest allowed `width / height`.
+    pub max_aspect: f32,
+}
+
+impl SizeHints {
+    /// Returns the largest size which honours the hints and fits inside `w` by `h`.
+    ///
+    /// Follows the rules of ICCCM 4.1.2.3, the same way dwm does. The min size is only
+    /// honoured as long as it fits.
+    #[must_use]
+    pub fn fit(&self, w: i32, h: i32) -> (i32, i32) {
+        let (slot_w, slot_h) = (w, h);
+        let (mut w, mut h) = (w, h);
+        // The base size is only ignored for the aspect ratio if it is not the min size.
+        let base_is_min = self.base_w == self.min_w && self.base_h == self.min_h;
+        if !base_is_min {
+            w -= self.base_w;
+            h -= self.base_h;
+        }
+        if self.min_aspect > 0.0 && self.max_aspect > 0.0 && w > 0 && h > 0 {
+            if self.max_aspect < w as f32 / h as f32 {
+                w = (h as f32 * self.max_aspect + 0.5) as i32;
+            } else if self.min_aspect > w as f32 / h as f32 {
+                h = (w as f32 / self.min_aspect + 0.5) as i32;
+            }
+        }
+        if base_is_min {
+            w -= self.base_w;
+            h -= self.base_h;
+        }
+        if self.inc_w > 0 {
+            w -= w % self.inc_w;
+        }
+        if self.inc_h > 0 {
+            h -= h % self.inc_h;
+        }
+        w = (w + self.base_w).max(self.min_w);
+        h = (h + self.base_h).max(self.min_h);
+        if self.max_w > 0 {
+            w = w.min(self.max_w);
+        }
+        if self.max_h > 0 {
+            h = h.min(self.max_h);
+        }
+        (w.min(slot_w), h.min(slot_h))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_should_round_down_to_increments_above_the_base_size() {
+        let hints = SizeHints {
+            base_w: 4,
+            base_h: 2,
+            inc_w: 10,
+            inc_h: 20,
+            ..SizeHints::default()
+        };
+        assert_eq!(hints.fit(999, 555), (994, 542));
+    }
+
+    #[test]
+    fn fit_should_honour_the_aspect_ratio() {
+        let hints = SizeHints {
+            min_aspect: 9.0 / 16.0,
+            max_aspect: 16.0 / 9.0,
+            ..SizeHints::default()
+        };
+        assert_eq!(hints.fit(1600, 600), (1067, 600));
+        assert_eq!(hints.fit(1000, 1000), (1000, 1000));
+        let fixed = SizeHints {
+            min_aspect: 16.0 / 9.0,
+            max_aspect: 16.0 / 9.0,
+            ..SizeHints::default()
+        };
+        assert_eq!(fixed.fit(1000, 1000), (1000, 563));
+    }
+
+    #[test]
+    fn fit_should_honour_the_max_size_but_never_exceed_the_slot() {
+        let hints = SizeHints {
+            min_w: 500,
+            max_h: 300,
+            ..SizeHints::default()
+        };
+        assert_eq!(hints.fit(400, 400), (400, 300));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::models::{Xyhw, XyhwBuilder};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Places a floating window can be snapped to within its workspace.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Center,
+    LeftHalf,
+    RightHalf,
+    TopHalf,
+    BottomHalf,
+}
+
+impl SnapPosition {
+    /// Returns the new location of `window` once snapped inside `area`.
+    ///
+    /// Corners and center keep the size of the window, halves resize it to fill
+    /// half of the area.
+    #[must_use]
+    pub fn snap(self, window: &Xyhw, area: &Xyhw) -> Xyhw {
+        let (w, h) = (window.w(), window.h());
+        let (left, top) = (area.x(), area.y());
+        let (right, bottom) = (area.x() + area.w(), area.y() + area.h());
+        let (half_w, half_h) = (area.w() / 2, area.h() / 2);
+        let (x, y, w, h) = match self {
+            Self::TopLeft => (left, top, w, h),
+            Self::TopRight => (right - w, top, w, h),
+            Self::BottomLeft => (left, bottom - h, w, h),
+            Self::BottomRight => (right - w, bottom - h, w, h),
+            Self::Center => (left + (area.w() - w) / 2, top + (area.h() - h) / 2, w, h),
+            Self::LeftHalf => (left, top, half_w, area.h()),
+            Self::RightHalf => (right - half_w, top, half_w, area.h()),
+            Self::TopHalf => (left, top, area.w(), half_h),
+            Self::BottomHalf => (left, bottom - half_h, area.w(), half_h),
+        };
+        XyhwBuilder {
+            x,
+            y,
+            h,
+            w,
+            ..XyhwBuilder::default()
+        }
+        .into()
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Could not parse snap position: {0}")]
+pub struct ParseSnapPositionError(String);
+
+impl FromStr for SnapPosition {
+    type Err = ParseSnapPositionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "TopLeft" => Ok(Self::TopLeft),
+            "TopRight" => Ok(Self::TopRight),
+            "BottomLeft" => Ok(Self::BottomLeft),
+            "BottomRight" => Ok(Self::BottomRight),
+            "Center" => Ok(Self::Center),
+            "LeftHalf" => Ok(Self::LeftHalf),
+            "RightHalf" => Ok(Self::RightHalf),
+            "TopHalf" => Ok(Self::TopHalf),
+            "BottomHalf" => Ok(Self::BottomHalf),
+            _ => Err(ParseSnapPositionError(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xyhw(x: i32, y: i32, w: i32, h: i32) -> Xyhw {
+        XyhwBuilder {
+            x,
+            y,
+            h,
+            w,
+            ..XyhwBuilder::default()
+        }
+        .into()
+    }
+
+    #[test]
+    fn corners_keep_the_window_size() {
+        let area = xyhw(0, 20, 1000, 800);
+        let window = xyhw(300, 300, 200, 100);
+        assert_eq!(
+            SnapPosition::TopLeft.snap(&window, &area),
+            xyhw(0, 20, 200, 100)
+        );
+        assert_eq!(
+            SnapPosition::BottomRight.snap(&window, &area),
+            xyhw(800, 720, 200, 100)
+        );
+        assert_eq!(
+            SnapPosition::Center.snap(&window, &area),
+            xyhw(400, 370, 200, 100)
+        );
+    }
+
+    #[test]
+    fn halves_fill_half_of_the_area() {
+        let area = xyhw(0, 20, 1000, 800);
+        let window = xyhw(300, 300, 200, 100);
+        assert_eq!(
+            SnapPosition::RightHalf.snap(&window, &area),
+            xyhw(500, 20, 500, 800)
+        );
+        assert_eq!(
+            SnapPosition::BottomHalf.snap(&window, &area),
+            xyhw(0, 420, 1000, 400)
+        );
+    }
+}
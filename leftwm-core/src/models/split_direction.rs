@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// How a workspace is divided by `SplitWorkspace`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitDirection {
+    /// The new workspace is placed to the right of the split one.
+    Horizontal,
+    /// The new workspace is placed below the split one.
+    Vertical,
+}
+
+#[derive(Debug, Error)]
+#[error("Could not parse split direction: {0}")]
+pub struct ParseSplitDirectionError(String);
+
+impl FromStr for SplitDirection {
+    type Err = ParseSplitDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Horizontal" => Ok(Self::Horizontal),
+            "Vertical" => Ok(Self::Vertical),
+            _ => Err(ParseSplitDirectionError(s.to_string())),
+        }
+    }
+}
@@ -26,6 +26,10 @@ use super::TagId;
 /// However, if there are more workspaces than there are tags, additional "unnamed" tags
 /// will be created automatically and appended to the list.
 ///
+/// With [`TagMode::PerMonitor`], each workspace owns a block of as many tags as configured,
+/// labelled the same way. The blocks follow each other in the list, the workspace keeps the
+/// offset of its block in `Workspace::tag_offset`.
+///
 /// ## Hidden Tags
 /// A hidden tag is a tag that is invisible and unknown to the user.
 /// Those tags are created in the source code and can be used for
@@ -172,6 +176,17 @@ impl Default for Tags {
     }
 }
 
+/// Whether the tags are shared by all workspaces, or each workspace has its own.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TagMode {
+    /// Any tag can be shown on any workspace, it moves to the focused one when selected.
+    #[default]
+    Shared,
+    /// Each workspace has its own tags, selecting a tag only affects the focused workspace.
+    PerMonitor,
+}
+
 /// A Tag is similar to a "Desktop".
 /// Each Screen/Workspace will always display a certain Tag.
 /// A Tag can not be displayed on more than one Workspace at a time.
@@ -270,8 +285,13 @@ impl Tag {
                 .iter_mut()
                 .filter(|w| w.has_tag(&self.id) && !w.is_unmanaged() && !w.floating())
                 .collect();
-            self.layout
-                .update_windows(workspace, &mut managed_nonfloat, self);
+            // zen mode only shows the focused window
+            let layout = if workspace.zen {
+                Layout::Monocle
+            } else {
+                self.layout
+            };
+            layout.update_windows(workspace, &mut managed_nonfloat, self);
             for w in &mut managed_nonfloat {
                 w.container_size = Some(workspace.xyhw);
             }
@@ -279,7 +299,10 @@ impl Tag {
             windows
                 .iter_mut()
                 .filter(|w| w.has_tag(&self.id) && !w.is_unmanaged() && w.floating())
-                .for_each(|w| w.normal = workspace.xyhw);
+                .for_each(|w| {
+                    w.normal = workspace.xyhw;
+                    w.apply_maximized(&workspace.xyhw_avoided());
+                });
         }
     }
 
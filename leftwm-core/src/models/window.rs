@@ -4,6 +4,7 @@ use super::WindowState;
 use super::WindowType;
 use crate::config::Config;
 use crate::models::Margins;
+use crate::models::SizeHints;
 use crate::models::TagId;
 use crate::models::Xyhw;
 use crate::models::XyhwBuilder;
@@ -35,6 +36,9 @@ pub struct Window {
     pub debugging: bool,
     pub name: Option<String>,
     pub pid: Option<u32>,
+    pub res_class: Option<String>,
+    /// The `_NET_WM_USER_TIME` of the window, the time of the last user input it received.
+    pub user_time: Option<u64>,
     pub r#type: WindowType,
     pub tags: Vec<TagId>,
     pub border: i32,
@@ -42,6 +46,7 @@ pub struct Window {
     pub margin_multiplier: f32,
     states: Vec<WindowState>,
     pub requested: Option<Xyhw>,
+    pub size_hints: Option<SizeHints>,
     pub normal: Xyhw,
     pub start_loc: Option<Xyhw>,
     pub container_size: Option<Xyhw>,
@@ -70,6 +75,9 @@ impl Window {
             states: vec![],
             normal: XyhwBuilder::default().into(),
             requested: None,
+            size_hints: None,
+            res_class: None,
+            user_time: None,
             floating: None,
             start_loc: None,
             container_size: None,
@@ -250,6 +258,33 @@ impl Window {
         value
     }
 
+    /// Shrinks the window to honour its size hints, keeping it centered in the space it was
+    /// given by the layout.
+    pub fn apply_size_hints(&mut self) {
+        if let Some(hints) = self.size_hints {
+            let (w, h) = (self.width(), self.height());
+            let (fit_w, fit_h) = hints.fit(w, h);
+            let (dw, dh) = ((w - fit_w).max(0), (h - fit_h).max(0));
+            self.normal.set_x(self.normal.x() + dw / 2);
+            self.normal.set_y(self.normal.y() + dh / 2);
+            self.normal.set_w(self.normal.w() - dw);
+            self.normal.set_h(self.normal.h() - dh);
+        }
+    }
+
+    /// Stretches a floating window over `area` along the axes it is maximized on.
+    pub fn apply_maximized(&mut self, area: &Xyhw) {
+        let offset = self.floating.unwrap_or_default();
+        if self.has_state(&WindowState::MaximizedVert) {
+            self.normal.set_y(area.y() - offset.y());
+            self.normal.set_h(area.h() - offset.h());
+        }
+        if self.has_state(&WindowState::MaximizedHorz) {
+            self.normal.set_x(area.x() - offset.x());
+            self.normal.set_w(area.w() - offset.w());
+        }
+    }
+
     pub fn set_x(&mut self, x: i32) {
         self.normal.set_x(x);
     }
@@ -4,7 +4,7 @@ use super::WindowHandle;
 use super::WindowState;
 use super::WindowType;
 use super::Xyhw;
-use crate::models::{Margins, XyhwChange};
+use crate::models::{Margins, SizeHints, XyhwChange};
 
 type MaybeName = Option<String>;
 
@@ -18,6 +18,8 @@ pub struct WindowChange {
     pub floating: Option<XyhwChange>,
     pub strut: Option<XyhwChange>,
     pub requested: Option<Xyhw>,
+    pub size_hints: Option<SizeHints>,
+    pub user_time: Option<u64>,
     pub states: Option<Vec<WindowState>>,
 }
 
@@ -33,6 +35,8 @@ impl WindowChange {
             floating: None,
             strut: None,
             requested: None,
+            size_hints: None,
+            user_time: None,
             states: None,
         }
     }
@@ -80,6 +84,14 @@ impl WindowChange {
         if let Some(requested) = self.requested {
             window.requested = Some(requested);
         }
+        if let Some(size_hints) = self.size_hints {
+            changed = changed || window.size_hints != Some(size_hints);
+            window.size_hints = Some(size_hints);
+        }
+        if let Some(user_time) = self.user_time {
+            // Doesn't need a redraw, it only matters for focus stealing.
+            window.user_time = Some(user_time);
+        }
         if let Some(r#type) = &self.r#type {
             let changed_type = &window.r#type != r#type;
             //if changed_type {
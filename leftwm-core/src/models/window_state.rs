@@ -13,4 +13,5 @@ pub enum WindowState {
     Fullscreen,
     Above,
     Below,
+    DemandsAttention,
 }
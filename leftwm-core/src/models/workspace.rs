@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Information for workspaces (screen divisions).
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Workspace {
     pub id: Option<i32>,
@@ -21,6 +22,22 @@ pub struct Workspace {
     pub xyhw: Xyhw,
     xyhw_avoided: Xyhw,
     pub max_window_width: Option<Size>,
+    /// Name of the `RandR` output the workspace is displayed on.
+    #[serde(default)]
+    pub output: Option<String>,
+    /// Number of tags before the ones owned by the workspace, with per monitor tags.
+    #[serde(default)]
+    pub tag_offset: usize,
+    /// Whether the struts of docks are ignored, letting windows cover the docks.
+    #[serde(default)]
+    pub ignore_struts: bool,
+    #[serde(default)]
+    pub hide_gutters: bool,
+    #[serde(default)]
+    pub hide_docks: bool,
+    /// Hides docks, gutters and margins, and only shows the focused window.
+    #[serde(default)]
+    pub zen: bool,
 }
 
 impl fmt::Debug for Workspace {
@@ -76,12 +93,59 @@ impl Workspace {
             }
             .into(),
             max_window_width,
+            output: None,
+            tag_offset: 0,
+            ignore_struts: false,
+            hide_gutters: false,
+            hide_docks: false,
+            zen: false,
+        }
+    }
+
+    /// Moves and resizes the workspace, when its monitor was reconfigured.
+    pub fn set_bbox(&mut self, bbox: BBox) {
+        self.xyhw = XyhwBuilder {
+            h: bbox.height,
+            w: bbox.width,
+            x: bbox.x,
+            y: bbox.y,
+            ..XyhwBuilder::default()
+        }
+        .into();
+        self.update_avoided_areas();
+    }
+
+    /// Returns the area of the workspace, as the bounding box of a screen.
+    #[must_use]
+    pub fn bbox(&self) -> BBox {
+        BBox {
+            x: self.xyhw.x(),
+            y: self.xyhw.y(),
+            width: self.xyhw.w(),
+            height: self.xyhw.h(),
         }
     }
 
     pub fn load_config(&mut self, config: &impl Config) {
-        self.margin = config.workspace_margin().unwrap_or_else(|| Margins::new(0));
+        let output_config = self.output.as_ref().and_then(|output| {
+            config
+                .workspaces()?
+                .into_iter()
+                .find(|wsc| wsc.output.as_ref() == Some(output))
+        });
+        self.margin = output_config
+            .as_ref()
+            .and_then(|wsc| wsc.margin)
+            .or_else(|| config.workspace_margin())
+            .unwrap_or_else(|| Margins::new(0));
         self.gutters = self.get_gutters_for_theme(config);
+        for gutter in output_config
+            .and_then(|wsc| wsc.gutters)
+            .unwrap_or_default()
+        {
+            self.gutters.retain(|g| g.side != gutter.side);
+            self.gutters.push(gutter);
+        }
     }
 
     pub fn get_gutters_for_theme(&mut self, config: &impl Config) -> Vec<Gutter> {
@@ -140,7 +204,7 @@ impl Workspace {
     pub fn x(&self) -> i32 {
         let left = self.margin.left as f32;
         let gutter = self.get_gutter(&Side::Left);
-        self.xyhw_avoided.x() + (self.margin_multiplier * left) as i32 + gutter
+        self.xyhw_avoided.x() + (self.margin_multiplier() * left) as i32 + gutter
     }
 
     /// Returns the x position for the workspace,
@@ -157,7 +221,7 @@ impl Workspace {
     pub fn y(&self) -> i32 {
         let top = self.margin.top as f32;
         let gutter = self.get_gutter(&Side::Top);
-        self.xyhw_avoided.y() + (self.margin_multiplier * top) as i32 + gutter
+        self.xyhw_avoided.y() + (self.margin_multiplier() * top) as i32 + gutter
     }
 
     #[must_use]
@@ -166,7 +230,7 @@ impl Workspace {
         let bottom = self.margin.bottom as f32;
         //Only one side
         let gutter = self.get_gutter(&Side::Top) + self.get_gutter(&Side::Bottom);
-        self.xyhw_avoided.h() - (self.margin_multiplier * (top + bottom)) as i32 - gutter
+        self.xyhw_avoided.h() - (self.margin_multiplier() * (top + bottom)) as i32 - gutter
     }
 
     /// Returns the original width for the workspace,
@@ -177,7 +241,7 @@ impl Workspace {
         let right = self.margin.right as f32;
         //Only one side
         let gutter = self.get_gutter(&Side::Left) + self.get_gutter(&Side::Right);
-        self.xyhw_avoided.w() - (self.margin_multiplier * (left + right)) as i32 - gutter
+        self.xyhw_avoided.w() - (self.margin_multiplier() * (left + right)) as i32 - gutter
     }
 
     /// Returns the width of the workspace,
@@ -192,17 +256,38 @@ impl Workspace {
     }
 
     fn get_gutter(&self, side: &Side) -> i32 {
+        if self.hide_gutters || self.zen {
+            return 0;
+        }
         match self.gutters.iter().find(|g| &g.side == side) {
             Some(g) => g.value,
             None => 0,
         }
     }
 
+    /// Returns the area of the workspace which is not covered by docks.
+    #[must_use]
+    pub const fn xyhw_avoided(&self) -> Xyhw {
+        self.xyhw_avoided
+    }
+
     #[must_use]
     pub fn center_halfed(&self) -> Xyhw {
         self.xyhw_avoided.center_halfed()
     }
 
+    /// Whether the workspace avoids the struts of docks.
+    #[must_use]
+    pub const fn avoids_struts(&self) -> bool {
+        !(self.ignore_struts || self.hide_docks || self.zen)
+    }
+
+    /// Whether the docks of the workspace are shown.
+    #[must_use]
+    pub const fn shows_docks(&self) -> bool {
+        !(self.hide_docks || self.zen)
+    }
+
     pub fn update_avoided_areas(&mut self) {
         let mut xyhw = self.xyhw;
         for a in &self.avoid {
@@ -216,9 +301,12 @@ impl Workspace {
         self.margin_multiplier = margin_multiplier;
     }
 
-    /// Get a reference to the tag model's margin multiplier.
+    /// Get a reference to the tag model's margin multiplier, margins are removed in zen mode.
     #[must_use]
-    pub const fn margin_multiplier(&self) -> f32 {
+    pub fn margin_multiplier(&self) -> f32 {
+        if self.zen {
+            return 0.0;
+        }
         self.margin_multiplier
     }
 
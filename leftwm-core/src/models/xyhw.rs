@@ -255,6 +255,28 @@ impl Xyhw {
         without
     }
 
+    /// Returns the area shared by this Xyhw and `other`.
+    #[must_use]
+    pub fn overlap(&self, other: &Self) -> u64 {
+        let w = cmp::min(self.x + self.w, other.x + other.w) - cmp::max(self.x, other.x);
+        let h = cmp::min(self.y + self.h, other.y + other.h) - cmp::max(self.y, other.y);
+        if w <= 0 || h <= 0 {
+            return 0;
+        }
+        w as u64 * h as u64
+    }
+
+    /// Shrink and shift this Xyhw so that it fits entirely inside `outer`.
+    #[must_use]
+    pub fn clamped_to(&self, outer: &Self) -> Self {
+        let mut clamped = *self;
+        clamped.w = cmp::min(self.w, outer.w);
+        clamped.h = cmp::min(self.h, outer.h);
+        clamped.x = cmp::max(outer.x, cmp::min(self.x, outer.x + outer.w - clamped.w));
+        clamped.y = cmp::max(outer.y, cmp::min(self.y, outer.y + outer.h - clamped.h));
+        clamped
+    }
+
     #[must_use]
     pub fn center_halfed(&self) -> Self {
         XyhwBuilder {
@@ -307,6 +329,51 @@ mod tests {
         assert_eq!(result, correct);
     }
 
+    #[test]
+    fn clamped_to_should_shift_and_shrink_into_outer() {
+        let outer = Xyhw {
+            x: 0,
+            y: 20,
+            w: 1000,
+            h: 800,
+            ..Xyhw::default()
+        };
+        let shifted = Xyhw {
+            x: 900,
+            y: 0,
+            w: 200,
+            h: 100,
+            ..Xyhw::default()
+        };
+        assert_eq!(
+            shifted.clamped_to(&outer),
+            Xyhw {
+                x: 800,
+                y: 20,
+                w: 200,
+                h: 100,
+                ..Xyhw::default()
+            }
+        );
+        let oversized = Xyhw {
+            x: -50,
+            y: 100,
+            w: 2000,
+            h: 100,
+            ..Xyhw::default()
+        };
+        assert_eq!(
+            oversized.clamped_to(&outer),
+            Xyhw {
+                x: 0,
+                y: 100,
+                w: 1000,
+                h: 100,
+                ..Xyhw::default()
+            }
+        );
+    }
+
     #[test]
     fn without_should_trim_from_the_top() {
         let a = Xyhw {
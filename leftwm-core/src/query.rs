@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Information clients can ask the manager for, answered as JSON.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Query {
+    Windows,
+    Workspaces,
+    Tags,
+    Focused,
+    Keybinds,
+    Layouts,
+}
@@ -0,0 +1,357 @@
+//! Runs the keybinds of a config in a headless `LeftWM`, to find configs that load fine but
+//! break at runtime.
+//!
+//! The manager is fed synthetic screens and windows, then each command is run on a fresh copy
+//! of it and the resulting state is checked.
+use crate::config::{self, Config};
+use crate::display_servers::{DisplayServer, HeadlessDisplayServer};
+use crate::models::{BBox, Screen, WindowHandle, WindowType};
+use crate::{resolve_alias, Command, DisplayEvent, Manager, State, Window};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+
+/// Size of the synthetic screens, used when the config doesn't place its workspaces.
+const SCREEN_SIZE: (i32, i32) = (1920, 1080);
+/// Number of synthetic windows opened on each screen.
+const WINDOWS_PER_SCREEN: usize = 2;
+
+type SimulatedManager<C> = Manager<C, HeadlessDisplayServer>;
+
+/// A problem found while simulating.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    /// The command that caused the problem, `None` if it happened while setting up.
+    pub command: Option<Command>,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.command {
+            Some(command) => write!(f, "{:?}: {}", command, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// The outcome of [`simulate`].
+#[derive(Debug, Default)]
+pub struct Simulation {
+    /// The commands that were run.
+    pub commands: Vec<Command>,
+    /// Commands that were not run because they affect the system outside of the manager, like
+    /// `Execute`.
+    pub skipped: Vec<Command>,
+    pub problems: Vec<Problem>,
+}
+
+/// Simulate `LeftWM` with `config`, running the command of each of its keybinds.
+///
+/// Commands are run twice in a row, as some only break when repeated. Panics are caught and
+/// reported as problems.
+pub fn simulate<C: Config + Clone>(config: &C) -> Simulation {
+    let mut simulation = Simulation::default();
+    let initial_problems = match catch_panic(|| check_state(&setup(config.clone()).state)) {
+        Ok(problems) => problems,
+        Err(panic) => {
+            simulation.problems.push(Problem {
+                command: None,
+                message: panic,
+            });
+            return simulation;
+        }
+    };
+    simulation.problems = initial_problems
+        .iter()
+        .map(|message| Problem {
+            command: None,
+            message: message.clone(),
+        })
+        .collect();
+
+    for keybind in config.mapped_bindings() {
+        let command = keybind.command;
+        if has_side_effects(config, &command) {
+            simulation.skipped.push(command);
+            continue;
+        }
+        let run = catch_panic(|| {
+            let mut manager = setup(config.clone());
+            for _ in 0..2 {
+                manager.command_handler(&command);
+                render(&mut manager);
+            }
+            check_state(&manager.state)
+        });
+        let messages = run.unwrap_or_else(|panic| vec![panic]);
+        // Problems of the initial state are reported once, not for every command.
+        let new_problems = messages
+            .into_iter()
+            .filter(|message| !initial_problems.contains(message))
+            .map(|message| Problem {
+                command: Some(command.clone()),
+                message,
+            });
+        simulation.problems.extend(new_problems);
+        simulation.commands.push(command);
+    }
+    simulation
+}
+
+/// Commands that spawn processes or write files when they are run.
+fn has_side_effects(config: &impl Config, command: &Command) -> bool {
+    match command {
+        Command::Execute(_)
+        | Command::ToggleScratchPad(_)
+        | Command::SoftReload
+        | Command::Other(_) => true,
+        Command::Sequence(commands) => commands.iter().any(|c| has_side_effects(config, c)),
+        Command::Alias(name) => matches!(
+            resolve_alias(config.aliases(), name),
+            Ok(commands) if commands.iter().any(|c| has_side_effects(config, c))
+        ),
+        _ => false,
+    }
+}
+
+/// Build a manager with screens like the ones of the config, and windows on each of them.
+fn setup<C: Config>(config: C) -> SimulatedManager<C> {
+    let screens = screens(&config);
+    let mut manager = SimulatedManager::new(config);
+    for screen in &screens {
+        manager.display_event_handler(DisplayEvent::ScreenCreate(screen.clone()));
+    }
+    for screen in &screens {
+        let x = screen.bbox.x + screen.bbox.width / 2;
+        let y = screen.bbox.y + screen.bbox.height / 2;
+        manager.state.focus_workspace_under_cursor(x, y);
+        for _ in 0..WINDOWS_PER_SCREEN {
+            let window = synthetic_window(manager.state.windows.len() as i32 + 1);
+            manager.display_event_handler(DisplayEvent::WindowCreate(window, x, y));
+        }
+    }
+    render(&mut manager);
+    manager
+}
+
+fn synthetic_window(number: i32) -> Window {
+    let name = "window ".to_string() + &number.to_string();
+    Window::new(WindowHandle::MockHandle(number), Some(name), None)
+}
+
+/// The screens the display server would report: the workspaces of the config when they have a
+/// fixed geometry, or else a row of screens, one for each workspace following an output.
+fn screens(config: &impl Config) -> Vec<Screen> {
+    let workspaces = config.workspaces().unwrap_or_default();
+    if !config::Workspace::follow_monitors(&workspaces) {
+        return workspaces.iter().map(Screen::from).collect();
+    }
+    let outputs: Vec<Option<String>> = workspaces.into_iter().map(|wsc| wsc.output).collect();
+    let outputs = if outputs.is_empty() {
+        vec![None, None]
+    } else {
+        outputs
+    };
+    outputs
+        .into_iter()
+        .enumerate()
+        .map(|(index, output)| Screen {
+            output,
+            ..Screen::new(BBox {
+                x: index as i32 * SCREEN_SIZE.0,
+                y: 0,
+                width: SCREEN_SIZE.0,
+                height: SCREEN_SIZE.1,
+            })
+        })
+        .collect()
+}
+
+/// Do what the event loop does after a change: arrange the windows and perform the actions.
+fn render<C: Config>(manager: &mut SimulatedManager<C>) {
+    manager.update_windows();
+    while let Some(act) = manager.state.actions.pop_front() {
+        manager.display_server.execute_action(act);
+    }
+}
+
+/// Look for states the manager should never end up in.
+fn check_state(state: &State) -> Vec<String> {
+    let mut problems = vec![];
+    for (index, ws) in state.workspaces.iter().enumerate() {
+        let id = ws.id.unwrap_or_default();
+        if id < 0 || id as usize >= state.tags.len_normal() {
+            problems.push(format!(
+                "workspace {} has id {}, it needs to be lower than the number of tags ({})",
+                index + 1,
+                ws.id.unwrap_or_default(),
+                state.tags.len_normal()
+            ));
+        }
+        if state.workspaces[..index]
+            .iter()
+            .any(|other| other.id == ws.id)
+        {
+            problems.push(format!(
+                "workspace {} has the same id as another",
+                index + 1
+            ));
+        }
+        for tag in ws.tags.iter().filter(|tag| state.tags.get(**tag).is_none()) {
+            problems.push(format!(
+                "workspace {} shows tag {} which does not exist",
+                index + 1,
+                *tag
+            ));
+        }
+    }
+    if let Some(index) = state
+        .focus_manager
+        .workspace_history
+        .iter()
+        .find(|index| **index >= state.workspaces.len())
+    {
+        problems.push(format!("focused workspace {} does not exist", *index + 1));
+    }
+
+    let managed = state
+        .windows
+        .iter()
+        .filter(|w| w.r#type == WindowType::Normal);
+    for window in managed {
+        problems.extend(check_window(state, window));
+    }
+    problems
+}
+
+fn check_window(state: &State, window: &Window) -> Vec<String> {
+    let mut problems = vec![];
+    if window.tags.is_empty() {
+        problems.push(format!("{} has no tag", window_name(window)));
+    }
+    for id in &window.tags {
+        if let Some(tag) = state.tags.get(*id) {
+            let reachable = state
+                .workspaces
+                .iter()
+                .any(|ws| state.workspace_tags(ws).contains(&tag.id));
+            if !tag.hidden && !reachable {
+                problems.push(format!(
+                    "{} is on tag {} which no workspace can show",
+                    window_name(window),
+                    tag.id
+                ));
+            }
+        } else {
+            problems.push(format!(
+                "{} is on tag {} which does not exist",
+                window_name(window),
+                *id
+            ));
+        }
+    }
+    let (x, y) = window.calculated_xyhw().center();
+    if window.visible()
+        && !state
+            .workspaces
+            .iter()
+            .any(|ws| ws.xyhw.contains_point(x, y))
+    {
+        problems.push(format!(
+            "{} is shown outside of every workspace",
+            window_name(window)
+        ));
+    }
+    problems
+}
+
+fn window_name(window: &Window) -> &str {
+    window.name.as_deref().unwrap_or_default()
+}
+
+/// Run `f`, returning the message of its panic if it panics.
+///
+/// The default panic hook is silenced meanwhile, so the panic is only reported once.
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    let message = Arc::new(Mutex::new(String::new()));
+    let hook_message = message.clone();
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if let Ok(mut message) = hook_message.lock() {
+            *message = info.to_string();
+        }
+    }));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    panic::set_hook(default_hook);
+    result.map_err(|_| message.lock().map(|m| m.clone()).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TestConfig;
+
+    #[test]
+    fn workspace_ids_greater_than_the_tag_count_should_be_reported() {
+        let config = TestConfig {
+            tags: vec!["1".to_string(), "2".to_string()],
+            keybinds: vec![],
+            floating_placement: None,
+            workspaces: vec![
+                config::Workspace {
+                    width: 1000,
+                    height: 1000,
+                    id: Some(0),
+                    ..config::Workspace::default()
+                },
+                config::Workspace {
+                    x: 1000,
+                    width: 1000,
+                    height: 1000,
+                    id: Some(5),
+                    ..config::Workspace::default()
+                },
+            ],
+            tag_mode: config::TagMode::Shared,
+            aliases: std::collections::HashMap::new(),
+        };
+        let simulation = simulate(&config);
+        assert!(simulation
+            .problems
+            .iter()
+            .any(|p| p.command.is_none() && p.message.contains("has id 5")));
+    }
+
+    #[test]
+    fn keybinds_of_a_valid_config_should_run_without_problems() {
+        let mut config = Manager::new_test(vec!["1".to_string(), "2".to_string()]).config;
+        let commands = [
+            "GotoTag 2",
+            "SendWindowToTag 2",
+            "SwapScreens",
+            "MoveWindowToNextWorkspace",
+            "FocusWorkspacePrevious",
+            "CloseWindow",
+            "ToggleFloating",
+            "MoveFloating 50 -20",
+            "RotateTag",
+            "Execute rm -rf /",
+        ];
+        config.keybinds = commands
+            .iter()
+            .map(|command| crate::Keybind {
+                command: command.parse().unwrap(),
+                modifier: vec!["modkey".to_string()],
+                key: "x".to_string(),
+            })
+            .collect();
+        let simulation = simulate(&config);
+        assert_eq!(simulation.problems, vec![]);
+        assert_eq!(simulation.commands.len(), commands.len() - 1);
+        assert_eq!(
+            simulation.skipped,
+            vec![Command::Execute("rm -rf /".to_string())]
+        );
+    }
+}
@@ -4,11 +4,11 @@ use crate::config::{Config, ScratchPad};
 use crate::layouts::Layout;
 use crate::models::Screen;
 use crate::models::Size;
-use crate::models::Tags;
 use crate::models::Window;
 use crate::models::Workspace;
 use crate::models::{FocusManager, LayoutManager};
 use crate::models::{Mode, WindowHandle};
+use crate::models::{TagId, TagMode, Tags};
 use crate::DisplayAction;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
@@ -26,9 +26,13 @@ pub struct State {
     pub active_scratchpads: HashMap<String, Option<u32>>,
     pub actions: VecDeque<DisplayAction>,
     pub tags: Tags, // List of all known tags.
+    pub tag_mode: TagMode,
+    /// Number of tags owned by each workspace, with per monitor tags.
+    pub tags_per_monitor: usize,
     pub disable_current_tag_swap: bool,
     pub mousekey: String,
     pub max_window_width: Option<Size>,
+    pub honour_size_hints: bool,
     pub default_width: i32,
     pub default_height: i32,
 }
@@ -41,6 +45,7 @@ impl State {
             tags.add_new(label.as_str(), layout_manager.new_layout());
         });
         tags.add_new_hidden("NSP");
+        let tags_per_monitor = tags.len_normal();
 
         Self {
             focus_manager: FocusManager::new(config),
@@ -54,8 +59,11 @@ impl State {
             active_scratchpads: Default::default(),
             actions: Default::default(),
             tags,
+            tag_mode: config.tag_mode(),
+            tags_per_monitor,
             disable_current_tag_swap: config.disable_current_tag_swap(),
             max_window_width: config.max_window_width(),
+            honour_size_hints: config.honour_size_hints(),
             mousekey: config.mousekey(),
             default_width: config.default_width(),
             default_height: config.default_height(),
@@ -65,7 +73,7 @@ impl State {
     //sorts the windows and puts them in order of importance
     //keeps the order for each importance level
     pub fn sort_windows(&mut self) {
-        use crate::models::WindowType;
+        use crate::models::{WindowState, WindowType};
         //first dialogs and modals
         let (level1, other): (Vec<&Window>, Vec<&Window>) = self.windows.iter().partition(|w| {
             w.r#type == WindowType::Dialog
@@ -74,6 +82,16 @@ impl State {
                 || w.r#type == WindowType::Menu
         });
 
+        //then windows that should stay on top
+        let (above, other): (Vec<&Window>, Vec<&Window>) = other
+            .iter()
+            .partition(|w| w.r#type == WindowType::Normal && w.has_state(&WindowState::Above));
+
+        //windows that should stay below the others
+        let (below, other): (Vec<&Window>, Vec<&Window>) = other
+            .iter()
+            .partition(|w| w.r#type == WindowType::Normal && w.has_state(&WindowState::Below));
+
         //next floating
         let (level2, other): (Vec<&Window>, Vec<&Window>) = other
             .iter()
@@ -89,8 +107,10 @@ impl State {
         //build the updated window list
         self.windows = level1
             .iter()
+            .chain(above.iter())
             .chain(level2.iter())
             .chain(level3.iter())
+            .chain(below.iter())
             .chain(other.iter())
             .map(|&w| w.clone())
             .collect();
@@ -106,6 +126,100 @@ impl State {
         Some(())
     }
 
+    pub fn move_to_bottom(&mut self, handle: &WindowHandle) -> Option<()> {
+        let index = self.windows.iter().position(|w| &w.handle == handle)?;
+        let window = self.windows.remove(index);
+        self.windows.push(window);
+        self.sort_windows();
+        Some(())
+    }
+
+    /// Returns the tags a workspace can show: all of them, or its own with per monitor tags.
+    #[must_use]
+    pub fn workspace_tags(&self, workspace: &Workspace) -> Vec<TagId> {
+        match self.tag_mode {
+            TagMode::Shared => (1..=self.tags.len_normal()).collect(),
+            TagMode::PerMonitor => (1..=self.tags_per_monitor)
+                .map(|tag| workspace.tag_offset + tag)
+                .collect(),
+        }
+    }
+
+    /// Returns the tag selected by a command: with per monitor tags, the `tag`-th tag of the
+    /// focused workspace.
+    #[must_use]
+    pub fn monitor_tag(&self, tag: TagId) -> TagId {
+        match self.focus_manager.workspace(&self.workspaces) {
+            Some(ws) if self.tag_mode == TagMode::PerMonitor && tag <= self.tags_per_monitor => {
+                ws.tag_offset + tag
+            }
+            _ => tag,
+        }
+    }
+
+    /// Returns the index of the workspace owning a tag, with per monitor tags.
+    #[must_use]
+    pub fn tag_owner(&self, tag: TagId) -> Option<usize> {
+        if self.tag_mode != TagMode::PerMonitor {
+            return None;
+        }
+        self.workspaces
+            .iter()
+            .position(|ws| tag > ws.tag_offset && tag <= ws.tag_offset + self.tags_per_monitor)
+    }
+
+    /// Moves the windows of removed workspaces to the focused workspace: the ones they show, or
+    /// with per monitor tags the ones on any of their tags, which no workspace owns anymore.
+    pub(crate) fn adopt_windows(&mut self, removed: &[Workspace]) {
+        let focused = self.focus_manager.workspace(&self.workspaces);
+        let Some(target) = focused.and_then(|ws| ws.tags.first().copied()) else {
+            return;
+        };
+        let orphans: Vec<TagId> = removed
+            .iter()
+            .flat_map(|ws| match self.tag_mode {
+                TagMode::Shared => ws.tags.clone(),
+                TagMode::PerMonitor => self.workspace_tags(ws),
+            })
+            .collect();
+        for window in self
+            .windows
+            .iter_mut()
+            .filter(|w| !w.is_unmanaged() && orphans.iter().any(|tag| w.has_tag(tag)))
+        {
+            window.clear_tags();
+            window.tag(&target);
+            let act = DisplayAction::SetWindowTags(window.handle, vec![target]);
+            self.actions.push_back(act);
+        }
+    }
+
+    /// Asks a managed window to close, it is removed once the display server destroys it.
+    pub fn close_window(&mut self, handle: &WindowHandle) {
+        if self
+            .windows
+            .iter()
+            .any(|w| &w.handle == handle && !w.is_unmanaged())
+        {
+            self.actions.push_back(DisplayAction::KillWindow(*handle));
+        }
+    }
+
+    /// Removes a workspace of a split screen, keeping the focus history in sync.
+    pub(crate) fn remove_workspace(&mut self, index: usize) -> Workspace {
+        let workspace = self.workspaces.remove(index);
+        let history = &mut self.focus_manager.workspace_history;
+        *history = history
+            .iter()
+            .filter(|i| **i != index)
+            .map(|i| if *i > index { i - 1 } else { *i })
+            .collect();
+        if history.is_empty() && !self.workspaces.is_empty() {
+            history.push_front(0);
+        }
+        workspace
+    }
+
     pub fn update_static(&mut self) {
         let workspaces = self.workspaces.clone();
         self.windows
@@ -125,6 +239,16 @@ impl State {
     pub(crate) fn load_config(&mut self, config: &impl Config) {
         self.mousekey = config.mousekey();
         self.max_window_width = config.max_window_width();
+        self.honour_size_hints = config.honour_size_hints();
+        self.scratchpads = config.create_list_of_scratchpads();
+        self.layouts = config.layouts();
+        self.disable_current_tag_swap = config.disable_current_tag_swap();
+        self.default_width = config.default_width();
+        self.default_height = config.default_height();
+        self.focus_manager.behaviour = config.focus_behaviour();
+        self.focus_manager.focus_new_windows = config.focus_new_windows();
+        self.focus_manager.focus_stealing_policy = config.focus_stealing_policy();
+        self.focus_manager.focus_stealing_overrides = config.focus_stealing_overrides();
         for win in &mut self.windows {
             win.load_config(config);
         }
@@ -300,9 +300,20 @@ pub fn register_child_hook(flag: Arc<AtomicBool>) {
 /// Sends command to shell for execution
 /// Assumes STDIN/STDOUT unwanted.
 pub fn exec_shell(command: &str, children: &mut Children) -> Option<u32> {
+    exec_shell_with_env(command, &[], children)
+}
+
+/// Sends command to shell for execution, with these additional environment variables.
+/// Assumes STDIN/STDOUT unwanted.
+pub fn exec_shell_with_env(
+    command: &str,
+    env: &[(&str, String)],
+    children: &mut Children,
+) -> Option<u32> {
     let child = Command::new("sh")
         .arg("-c")
         .arg(&command)
+        .envs(env.iter().map(|(key, value)| (key, value)))
         .stdin(Stdio::null())
         .stdout(Stdio::null())
         .spawn()
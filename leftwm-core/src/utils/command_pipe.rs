@@ -1,9 +1,6 @@
 //! Creates a pipe to listen for external commands.
-use crate::layouts::Layout;
-use crate::models::TagId;
 use crate::Command;
 use std::path::{Path, PathBuf};
-use std::str::FromStr;
 use tokio::fs;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::sync::mpsc;
@@ -64,100 +61,15 @@ async fn read_from_pipe(pipe_file: &Path, tx: &mpsc::UnboundedSender<Command>) -
     let mut lines = BufReader::new(file).lines();
 
     while let Some(line) = lines.next_line().await.ok()? {
-        let cmd = match parse_command(&line) {
-            Ok(cmd) => cmd,
-            Err(err) => {
-                log::error!("An error occurred while parsing the command: {}", err);
-                return None;
-            }
-        };
-        tx.send(cmd).ok()?;
+        match line.parse::<Command>() {
+            Ok(cmd) => tx.send(cmd).ok()?,
+            Err(err) => log::error!("An error occurred while parsing the command: {}", err),
+        }
     }
 
     Some(())
 }
 
-fn parse_command(s: &str) -> Result<Command, Box<dyn std::error::Error>> {
-    let head = *s.split(' ').collect::<Vec<&str>>().get(0).unwrap_or(&"");
-    match head {
-        "SoftReload" => Ok(Command::SoftReload),
-        "ToggleFullScreen" => Ok(Command::ToggleFullScreen),
-        "ToggleSticky" => Ok(Command::ToggleSticky),
-        "SwapScreens" => Ok(Command::SwapScreens),
-        "MoveWindowToLastWorkspace" => Ok(Command::MoveWindowToLastWorkspace),
-        "MoveWindowToNextWorkspace" => Ok(Command::MoveWindowToNextWorkspace),
-        "MoveWindowToPreviousWorkspace" => Ok(Command::MoveWindowToPreviousWorkspace),
-        "FloatingToTile" => Ok(Command::FloatingToTile),
-        "TileToFloating" => Ok(Command::TileToFloating),
-        "ToggleFloating" => Ok(Command::ToggleFloating),
-        "MoveWindowUp" => Ok(Command::MoveWindowUp),
-        "MoveWindowDown" => Ok(Command::MoveWindowDown),
-        "FocusWindowUp" => Ok(Command::FocusWindowUp),
-        "MoveWindowTop" => Ok(Command::MoveWindowTop),
-        "FocusWindowDown" => Ok(Command::FocusWindowDown),
-        "FocusNextTag" => Ok(Command::FocusNextTag),
-        "FocusPreviousTag" => Ok(Command::FocusPreviousTag),
-        "FocusWorkspaceNext" => Ok(Command::FocusWorkspaceNext),
-        "FocusWorkspacePrevious" => Ok(Command::FocusWorkspacePrevious),
-        "NextLayout" => Ok(Command::NextLayout),
-        "PreviousLayout" => Ok(Command::PreviousLayout),
-        "RotateTag" => Ok(Command::RotateTag),
-        "CloseWindow" => Ok(Command::CloseWindow),
-        "ToggleScratchPad" => build_toggle_scratchpad(s),
-        "SendWorkspaceToTag" => build_send_workspace_to_tag(s),
-        "SendWindowToTag" => build_send_window_to_tag(s),
-        "SetLayout" => build_set_layout(s),
-        "SetMarginMultiplier" => build_set_margin_multiplier(s),
-        _ => Ok(Command::Other(s.into())),
-    }
-}
-
-fn build_toggle_scratchpad(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
-    let headless = without_head(raw, "ToggleScratchPad ");
-    let parts: Vec<&str> = headless.split(' ').collect();
-    let name = *parts.get(0).ok_or("missing argument scratchpad's name")?;
-    Ok(Command::ToggleScratchPad(name.to_string()))
-}
-
-fn build_send_window_to_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
-    let headless = without_head(raw, "SendWindowToTag ");
-    let parts: Vec<&str> = headless.split(' ').collect();
-    let tag_id: TagId = parts.get(0).ok_or("missing argument tag_id")?.parse()?;
-    Ok(Command::SendWindowToTag(tag_id))
-}
-
-fn build_send_workspace_to_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
-    let headless = without_head(raw, "SendWorkspaceToTag ");
-    let parts: Vec<&str> = headless.split(' ').collect();
-    let ws_index: usize = parts
-        .get(0)
-        .ok_or("missing argument workspace index")?
-        .parse()?;
-    let tag_index: usize = parts.get(1).ok_or("missing argument tag index")?.parse()?;
-    Ok(Command::SendWorkspaceToTag(ws_index, tag_index))
-}
-
-fn build_set_layout(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
-    let headless = without_head(raw, "SetLayout ");
-    let parts: Vec<&str> = headless.split(' ').collect();
-    let layout_name = *parts.get(0).ok_or("missing layout name")?;
-    Ok(Command::SetLayout(Layout::from_str(layout_name)?))
-}
-
-fn build_set_margin_multiplier(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
-    let headless = without_head(raw, "SetMarginMultiplier ");
-    let parts: Vec<&str> = headless.split(' ').collect();
-    let margin_multiplier = f32::from_str(parts.get(0).ok_or("missing argument multiplier")?)?;
-    Ok(Command::SetMarginMultiplier(margin_multiplier))
-}
-
-fn without_head<'a, 'b>(s: &'a str, head: &'b str) -> &'a str {
-    if !s.starts_with(head) {
-        return s;
-    }
-    &s[head.len()..]
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -199,11 +111,11 @@ mod test {
                 .open(&pipe_file)
                 .await
                 .unwrap();
-            pipe.write_all(b"Hello World\n").await.unwrap();
+            pipe.write_all(b"Hello World\nSoftReload\n").await.unwrap();
             pipe.flush().await.unwrap();
 
             assert_eq!(
-                Command::Other("Hello World".to_string()),
+                Command::SoftReload,
                 command_pipe.read_command().await.unwrap()
             );
         }
@@ -0,0 +1,342 @@
+//! Listens on a Unix socket for commands and queries, replying to each with its outcome.
+//!
+//! Each request and reply is one line of JSON. Unlike the command pipe, several clients can be
+//! connected at once, and each gets its own replies.
+//!
+//! A client sending `{"Subscribe": [..]}` gets the events of these kinds from then on, one line of
+//! JSON for each, instead of replies.
+use crate::{Command, CommandError, Event, EventKind, Query};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::path::PathBuf;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::unix::OwnedWriteHalf;
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{broadcast, mpsc, oneshot};
+
+/// Number of events kept for a subscriber that is slow to read them.
+const EVENT_BUFFER: usize = 256;
+
+/// A request sent to the socket, like `{"Command": "SendWindowToTag 2"}` or `"GetWindows"`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Request {
+    /// Run a command, written like in the command pipe.
+    Command(String),
+    GetWindows,
+    GetWorkspaces,
+    GetTags,
+    GetFocused,
+    GetKeybinds,
+    GetLayouts,
+    /// Get the events of these kinds, or of every kind if empty.
+    Subscribe(Vec<EventKind>),
+}
+
+/// The reply to a request, like `{"result": "success"}`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum Reply {
+    Success {
+        /// The answer to a query.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        data: Option<Value>,
+    },
+    /// The command was understood but could not be applied.
+    Failure { reason: String },
+    /// The request is not valid JSON, or its command could not be parsed.
+    ParseError { reason: String },
+}
+
+impl From<&Result<bool, CommandError>> for Reply {
+    fn from(result: &Result<bool, CommandError>) -> Self {
+        match result {
+            Ok(_) => Self::Success { data: None },
+            Err(err) => Self::Failure {
+                reason: err.to_string(),
+            },
+        }
+    }
+}
+
+impl From<serde_json::Result<Value>> for Reply {
+    fn from(answer: serde_json::Result<Value>) -> Self {
+        match answer {
+            Ok(data) => Self::Success { data: Some(data) },
+            Err(err) => Self::Failure {
+                reason: err.to_string(),
+            },
+        }
+    }
+}
+
+/// What a client asked the manager for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ManagerRequest {
+    Command(Command),
+    Query(Query),
+}
+
+/// A request received on the socket, waiting for its reply.
+#[derive(Debug)]
+pub struct PendingRequest {
+    pub request: ManagerRequest,
+    reply: oneshot::Sender<Reply>,
+}
+
+impl PendingRequest {
+    /// Send the reply to the client.
+    pub fn respond(self, reply: Reply) {
+        // The client may have disconnected already.
+        self.reply.send(reply).ok();
+    }
+}
+
+/// Holds the socket file location and a receiver for the requests of all clients.
+#[derive(Debug)]
+pub struct CommandSocket {
+    socket_file: PathBuf,
+    rx: mpsc::UnboundedReceiver<PendingRequest>,
+    events: broadcast::Sender<Event>,
+    listener: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for CommandSocket {
+    fn drop(&mut self) {
+        self.listener.abort();
+        std::fs::remove_file(&self.socket_file).ok();
+    }
+}
+
+impl CommandSocket {
+    /// Bind to the Unix socket and listen for clients.
+    /// # Errors
+    ///
+    /// Will error if the socket cannot be bound, likely a filesystem issue
+    /// such as inadequate permissions.
+    pub async fn new(socket_file: PathBuf) -> Result<Self, std::io::Error> {
+        tokio::fs::remove_file(&socket_file).await.ok();
+        let listener = UnixListener::bind(&socket_file)?;
+        let (tx, rx) = mpsc::unbounded_channel();
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        let subscriptions = events.clone();
+        let listener = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((peer, _)) => {
+                        tokio::spawn(serve_client(peer, tx.clone(), subscriptions.clone()));
+                    }
+                    Err(e) => log::error!("accept failed = {:?}", e),
+                }
+            }
+        });
+        Ok(Self {
+            socket_file,
+            rx,
+            events,
+            listener,
+        })
+    }
+
+    pub async fn read_request(&mut self) -> Option<PendingRequest> {
+        self.rx.recv().await
+    }
+
+    /// Send the event to the clients subscribed to it.
+    pub fn publish(&self, event: Event) {
+        // Fails only when no client is subscribed.
+        self.events.send(event).ok();
+    }
+}
+
+/// Answer the requests of one client until it disconnects.
+async fn serve_client(
+    peer: UnixStream,
+    tx: mpsc::UnboundedSender<PendingRequest>,
+    events: broadcast::Sender<Event>,
+) {
+    let (reader, mut writer) = peer.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Ok(Request::Subscribe(kinds)) = serde_json::from_str(&line) {
+            // Subscribe before replying, so no event is missed.
+            let receiver = events.subscribe();
+            if write_line(&mut writer, &Reply::Success { data: None }).await {
+                send_events(writer, receiver, &kinds).await;
+            }
+            return;
+        }
+        let reply = handle_request(&line, &tx).await;
+        if !write_line(&mut writer, &reply).await {
+            return;
+        }
+    }
+}
+
+/// Send the events of `kinds` to a subscriber until it disconnects.
+async fn send_events(
+    mut writer: OwnedWriteHalf,
+    mut receiver: broadcast::Receiver<Event>,
+    kinds: &[EventKind],
+) {
+    loop {
+        match receiver.recv().await {
+            Ok(event) => {
+                let wanted = kinds.is_empty() || kinds.contains(&event.kind());
+                if wanted && !write_line(&mut writer, &event).await {
+                    return;
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                log::warn!("A subscriber missed {} events", missed);
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
+
+/// Write `value` as a line of JSON, returning whether the client got it.
+async fn write_line(writer: &mut OwnedWriteHalf, value: &(impl Serialize + fmt::Debug)) -> bool {
+    let mut json = match serde_json::to_string(value) {
+        Ok(json) => json,
+        Err(err) => {
+            log::error!("Cannot serialize {:?}: {}", value, err);
+            return false;
+        }
+    };
+    json.push('\n');
+    writer.write_all(json.as_bytes()).await.is_ok()
+}
+
+async fn handle_request(line: &str, tx: &mpsc::UnboundedSender<PendingRequest>) -> Reply {
+    let request = match parse_request(line) {
+        Ok(request) => request,
+        Err(reason) => return Reply::ParseError { reason },
+    };
+    let (reply, outcome) = oneshot::channel();
+    if tx.send(PendingRequest { request, reply }).is_err() {
+        return Reply::Failure {
+            reason: "LeftWM is shutting down".to_string(),
+        };
+    }
+    outcome.await.unwrap_or_else(|_| Reply::Failure {
+        reason: "LeftWM dropped the request".to_string(),
+    })
+}
+
+fn parse_request(line: &str) -> Result<ManagerRequest, String> {
+    let query = match serde_json::from_str(line).map_err(|e| e.to_string())? {
+        Request::Command(command) => {
+            let command = command.parse::<Command>().map_err(|e| e.to_string())?;
+            return Ok(ManagerRequest::Command(command));
+        }
+        Request::GetWindows => Query::Windows,
+        Request::GetWorkspaces => Query::Workspaces,
+        Request::GetTags => Query::Tags,
+        Request::GetFocused => Query::Focused,
+        Request::GetKeybinds => Query::Keybinds,
+        Request::GetLayouts => Query::Layouts,
+        // Handled by `serve_client`, as it changes what the client gets.
+        Request::Subscribe(_) => return Err("Unexpected subscription".to_string()),
+    };
+    Ok(ManagerRequest::Query(query))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::helpers::test::temp_path;
+    use tokio::io::Lines;
+    use tokio::net::unix::OwnedReadHalf;
+
+    async fn connect(socket_file: &PathBuf) -> (Lines<BufReader<OwnedReadHalf>>, OwnedWriteHalf) {
+        let (reader, writer) = UnixStream::connect(socket_file).await.unwrap().into_split();
+        (BufReader::new(reader).lines(), writer)
+    }
+
+    async fn request(
+        client: &mut (Lines<BufReader<OwnedReadHalf>>, OwnedWriteHalf),
+        line: &str,
+    ) -> Reply {
+        client.1.write_all(line.as_bytes()).await.unwrap();
+        client.1.write_all(b"\n").await.unwrap();
+        let reply = client.0.next_line().await.unwrap().unwrap();
+        serde_json::from_str(&reply).unwrap()
+    }
+
+    #[tokio::test]
+    async fn each_client_should_get_the_outcome_of_its_commands() {
+        let socket_file = temp_path().await.unwrap();
+        let mut command_socket = CommandSocket::new(socket_file.clone()).await.unwrap();
+        tokio::spawn(async move {
+            while let Some(pending) = command_socket.read_request().await {
+                let reply = match pending.request {
+                    ManagerRequest::Command(Command::GotoTag(12)) => {
+                        Reply::from(&Err(CommandError::NoSuchTag(12)))
+                    }
+                    ManagerRequest::Command(_) => Reply::from(&Ok(true)),
+                    ManagerRequest::Query(query) => Reply::from(serde_json::to_value(query)),
+                };
+                pending.respond(reply);
+            }
+        });
+
+        let mut first = connect(&socket_file).await;
+        let mut second = connect(&socket_file).await;
+        assert_eq!(
+            request(&mut second, r#"{"Command": "GotoTag 12"}"#).await,
+            Reply::Failure {
+                reason: "Tag 12 does not exist".to_string()
+            }
+        );
+        assert_eq!(
+            request(&mut first, r#"{"Command": "GotoTag 2"}"#).await,
+            Reply::Success { data: None }
+        );
+        assert_eq!(
+            request(&mut second, r#""GetTags""#).await,
+            Reply::Success {
+                data: Some(Value::from("Tags"))
+            }
+        );
+        assert!(matches!(
+            request(&mut first, r#"{"Command": "GotoTag two"}"#).await,
+            Reply::ParseError { .. }
+        ));
+        assert!(matches!(
+            request(&mut second, "GotoTag 2").await,
+            Reply::ParseError { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn subscribers_should_only_get_the_events_they_asked_for() {
+        let socket_file = temp_path().await.unwrap();
+        let command_socket = CommandSocket::new(socket_file.clone()).await.unwrap();
+        let mut client = connect(&socket_file).await;
+        assert_eq!(
+            request(&mut client, r#"{"Subscribe": ["WindowClosed"]}"#).await,
+            Reply::Success { data: None }
+        );
+
+        command_socket.publish(Event::TitleChanged {
+            window: 3,
+            title: None,
+        });
+        command_socket.publish(Event::WindowClosed { window: 3 });
+        let line = client.0.next_line().await.unwrap().unwrap();
+        assert_eq!(line, r#"{"event":"WindowClosed","window":3}"#);
+    }
+
+    #[tokio::test]
+    async fn socket_cleanup() {
+        let socket_file = temp_path().await.unwrap();
+        let command_socket = CommandSocket::new(socket_file.clone()).await.unwrap();
+        assert!(socket_file.exists());
+        drop(command_socket);
+        assert!(!socket_file.exists());
+    }
+}
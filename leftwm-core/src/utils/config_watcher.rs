@@ -0,0 +1,134 @@
+//! Watches the config files with inotify to reload them when they change.
+use nix::sys::inotify::{AddWatchFlags, InitFlags, Inotify, WatchDescriptor};
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use tokio::io::unix::AsyncFd;
+use tokio::time::{sleep, Duration};
+
+/// Time to wait for more events once a file changed, as editors usually write in several steps.
+const SETTLE_TIME: Duration = Duration::from_millis(100);
+
+/// Holds the inotify instance and the names of the watched files in each directory.
+///
+/// The directories are watched rather than the files themselves, so files replaced by editors
+/// through a rename keep being watched.
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    inotify: AsyncFd<Inotify>,
+    files: HashMap<WatchDescriptor, HashSet<OsString>>,
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        nix::unistd::close(self.inotify.as_raw_fd()).ok();
+    }
+}
+
+impl ConfigWatcher {
+    /// Watch the given files. Files in directories that don't exist are skipped.
+    /// # Errors
+    ///
+    /// Will error if inotify cannot be initialised, likely because the limit of inotify instances
+    /// is reached.
+    pub fn new(files: &[PathBuf]) -> std::io::Result<Self> {
+        let inotify = Inotify::init(InitFlags::IN_NONBLOCK | InitFlags::IN_CLOEXEC)?;
+        let mut watched: HashMap<WatchDescriptor, HashSet<OsString>> = HashMap::new();
+        let named_files = files
+            .iter()
+            .filter_map(|file| Some((file.parent()?, file.file_name()?)));
+        for (dir, name) in named_files {
+            let dir = if dir == Path::new("") {
+                Path::new(".")
+            } else {
+                dir
+            };
+            match inotify.add_watch(dir, watch_flags()) {
+                Ok(wd) => {
+                    watched.entry(wd).or_default().insert(name.to_os_string());
+                }
+                Err(err) => log::warn!("Cannot watch {}: {}", dir.display(), err),
+            }
+        }
+        let inotify = match AsyncFd::new(inotify) {
+            Ok(inotify) => inotify,
+            Err(err) => {
+                nix::unistd::close(inotify.as_raw_fd()).ok();
+                return Err(err);
+            }
+        };
+        Ok(Self {
+            inotify,
+            files: watched,
+        })
+    }
+
+    /// Wait until one of the watched files changed.
+    pub async fn wait_for_change(&mut self) {
+        while !self.read_changes().await {}
+        // Let the writer finish before reporting the change.
+        sleep(SETTLE_TIME).await;
+        while self.try_read_changes().is_some() {}
+    }
+
+    /// Wait for inotify events, returning whether they concern a watched file.
+    async fn read_changes(&mut self) -> bool {
+        let mut guard = match self.inotify.readable().await {
+            Ok(guard) => guard,
+            Err(err) => {
+                log::error!("Cannot read inotify events: {}", err);
+                return std::future::pending().await;
+            }
+        };
+        if let Some(changed) = self.try_read_changes() {
+            changed
+        } else {
+            guard.clear_ready();
+            false
+        }
+    }
+
+    /// Read the pending inotify events, `None` if there are none.
+    fn try_read_changes(&self) -> Option<bool> {
+        let events = self.inotify.get_ref().read_events().ok()?;
+        Some(
+            events
+                .iter()
+                .any(|event| match (self.files.get(&event.wd), &event.name) {
+                    (Some(names), Some(name)) => names.contains(name),
+                    _ => false,
+                }),
+        )
+    }
+}
+
+fn watch_flags() -> AddWatchFlags {
+    AddWatchFlags::IN_CLOSE_WRITE
+        | AddWatchFlags::IN_MOVED_TO
+        | AddWatchFlags::IN_CREATE
+        | AddWatchFlags::IN_DELETE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn only_changes_to_watched_files_should_be_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config.toml");
+        std::fs::write(&config, "modkey = \"Mod1\"").unwrap();
+        let mut watcher = ConfigWatcher::new(std::slice::from_ref(&config)).unwrap();
+
+        std::fs::write(dir.path().join("other.toml"), "").unwrap();
+        let unrelated = tokio::time::timeout(SETTLE_TIME * 2, watcher.wait_for_change()).await;
+        assert!(unrelated.is_err());
+
+        std::fs::write(&config, "modkey = \"Mod4\"").unwrap();
+        tokio::time::timeout(Duration::from_secs(5), watcher.wait_for_change())
+            .await
+            .expect("the change should be reported");
+        assert!(watcher.try_read_changes().is_none());
+    }
+}
@@ -0,0 +1,257 @@
+//! Runs the hooks of the config when events happen.
+//!
+//! The commands get the event in these environment variables, when it is about them:
+//! `LEFTWM_EVENT`, `LEFTWM_WINDOW_ID`, `LEFTWM_WINDOW_CLASS`, `LEFTWM_WINDOW_TITLE`,
+//! `LEFTWM_WORKSPACE`, `LEFTWM_TAG` (labels of the tags shown, separated by spaces) and
+//! `LEFTWM_LAYOUT`.
+//!
+//! Placeholders like `{window_class}` are expanded in the commands, see
+//! [`crate::utils::placeholders`].
+//!
+//! A hook runs at most once every [`COOLDOWN`]. When its events come faster, only the last one
+//! is run, once the cooldown is over. `on_new_window` and `on_window_close` are about a window
+//! each time, so their cooldown is per window and no window is missed.
+use crate::config::Hooks;
+use crate::models::dto::window_id;
+use crate::utils::child_process::{exec_shell_with_env, Children};
+use crate::utils::placeholders;
+use crate::{Event, State};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// The shortest time between two runs of the same hook.
+pub const COOLDOWN: Duration = Duration::from_millis(200);
+
+/// A hook command and its environment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invocation {
+    pub command: String,
+    pub env: Vec<(&'static str, String)>,
+}
+
+/// A hook, with the window it ran for when it runs once per window.
+type HookKey = (&'static str, Option<u64>);
+
+/// Remembers when each hook last ran, and the runs waiting for their cooldown.
+#[derive(Debug, Default)]
+pub struct HookRunner {
+    last_run: HashMap<HookKey, Instant>,
+    delayed: HashMap<HookKey, (Instant, Invocation)>,
+}
+
+impl HookRunner {
+    /// Run the hook of `event`, or delay it if the hook ran recently.
+    pub fn handle(&mut self, hooks: &Hooks, state: &State, event: &Event, children: &mut Children) {
+        if let Some((name, command)) = hooks.for_event(event) {
+            let (command, placeholders) = placeholders::expand(command, state);
+            let mut env = environment(state, event);
+            env.extend(placeholders);
+            let invocation = Invocation { command, env };
+            let window = match event {
+                Event::WindowCreated { window } => Some(window.handle),
+                Event::WindowClosed { window } => Some(*window),
+                _ => None,
+            };
+            if let Some(invocation) = self.schedule((name, window), invocation, Instant::now()) {
+                run(&invocation, children);
+            }
+        }
+    }
+
+    /// Returns the invocation if it can run `now`, otherwise delays it, replacing the run
+    /// already delayed.
+    fn schedule(
+        &mut self,
+        key: HookKey,
+        invocation: Invocation,
+        now: Instant,
+    ) -> Option<Invocation> {
+        // forget the runs that no longer delay anything, there is one per window
+        self.last_run
+            .retain(|_, last_run| now < *last_run + COOLDOWN);
+        let ready_at = self
+            .last_run
+            .get(&key)
+            .map_or(now, |last_run| *last_run + COOLDOWN);
+        if ready_at <= now {
+            self.last_run.insert(key, now);
+            self.delayed.remove(&key);
+            return Some(invocation);
+        }
+        self.delayed.insert(key, (ready_at, invocation));
+        None
+    }
+
+    /// Take the delayed invocations that can run `now`.
+    fn take_due(&mut self, now: Instant) -> Vec<Invocation> {
+        let due: Vec<HookKey> = self
+            .delayed
+            .iter()
+            .filter(|(_, (ready_at, _))| *ready_at <= now)
+            .map(|(key, _)| *key)
+            .collect();
+        due.into_iter()
+            .filter_map(|key| {
+                let (_, invocation) = self.delayed.remove(&key)?;
+                self.last_run.insert(key, now);
+                Some(invocation)
+            })
+            .collect()
+    }
+
+    /// Run the delayed hooks whose cooldown is over.
+    pub fn run_due(&mut self, children: &mut Children) {
+        for invocation in self.take_due(Instant::now()) {
+            run(&invocation, children);
+        }
+    }
+
+    /// Wait until a delayed hook can run, forever if there is none.
+    pub async fn wait_for_due(&self) {
+        match self.delayed.values().map(|(ready_at, _)| *ready_at).min() {
+            Some(ready_at) => tokio::time::sleep_until(ready_at.into()).await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+fn run(invocation: &Invocation, children: &mut Children) {
+    if exec_shell_with_env(&invocation.command, &invocation.env, children).is_none() {
+        log::error!("Cannot run the hook {:?}", invocation.command);
+    }
+}
+
+/// The environment variables describing `event`.
+fn environment(state: &State, event: &Event) -> Vec<(&'static str, String)> {
+    let mut env = vec![("LEFTWM_EVENT", format!("{:?}", event.kind()))];
+    let window = match event {
+        Event::WindowCreated { window }
+        | Event::FocusChanged {
+            window: Some(window),
+        } => Some(window.handle),
+        Event::WindowClosed { window }
+        | Event::TitleChanged { window, .. }
+        | Event::UrgencyChanged { window, .. } => Some(*window),
+        _ => None,
+    };
+    if let Some(id) = window {
+        env.push(("LEFTWM_WINDOW_ID", id.to_string()));
+        let window = state.windows.iter().find(|w| window_id(w.handle) == id);
+        if let Some(window) = window {
+            let class = window.res_class.clone().unwrap_or_default();
+            env.push(("LEFTWM_WINDOW_CLASS", class));
+            env.push((
+                "LEFTWM_WINDOW_TITLE",
+                window.name.clone().unwrap_or_default(),
+            ));
+        }
+    }
+
+    let workspace = match event {
+        Event::TagChanged { workspace, .. } | Event::LayoutChanged { workspace, .. } => {
+            Some(*workspace)
+        }
+        Event::WorkspaceChanged { workspace } => Some(workspace.index),
+        _ => state.focus_manager.workspace_history.front().copied(),
+    };
+    if let Some((index, ws)) =
+        workspace.and_then(|index| Some((index, state.workspaces.get(index)?)))
+    {
+        let labels: Vec<&str> = ws
+            .tags
+            .iter()
+            .filter_map(|tag| state.tags.get(*tag))
+            .map(|tag| tag.label.as_str())
+            .collect();
+        env.push(("LEFTWM_WORKSPACE", index.to_string()));
+        env.push(("LEFTWM_TAG", labels.join(" ")));
+        env.push(("LEFTWM_LAYOUT", format!("{:?}", ws.layout)));
+    }
+    env
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Screen, WindowHandle};
+    use crate::{Manager, Window};
+
+    fn invocation(command: &str) -> Invocation {
+        Invocation {
+            command: command.to_string(),
+            env: vec![],
+        }
+    }
+
+    #[test]
+    fn hooks_running_too_often_should_only_run_their_last_event() {
+        let mut runner = HookRunner::default();
+        let start = Instant::now();
+        let later = |millis| start + Duration::from_millis(millis);
+        assert!(runner
+            .schedule(("on_focus_change", None), invocation("1"), start)
+            .is_some());
+        assert!(runner
+            .schedule(("on_tag_change", None), invocation("a"), later(10))
+            .is_some());
+        assert!(runner
+            .schedule(("on_focus_change", None), invocation("2"), later(50))
+            .is_none());
+        assert!(runner
+            .schedule(("on_focus_change", None), invocation("3"), later(100))
+            .is_none());
+
+        assert_eq!(runner.take_due(later(150)), vec![]);
+        assert_eq!(runner.take_due(later(200)), vec![invocation("3")]);
+        assert_eq!(runner.take_due(later(1000)), vec![]);
+        assert!(runner
+            .schedule(("on_focus_change", None), invocation("4"), later(300))
+            .is_none());
+        assert!(runner
+            .schedule(("on_focus_change", None), invocation("5"), later(400))
+            .is_some());
+        assert_eq!(runner.take_due(later(1000)), vec![]);
+    }
+
+    #[test]
+    fn hooks_about_windows_should_run_for_each_window() {
+        let mut runner = HookRunner::default();
+        let start = Instant::now();
+        for id in 1..=3 {
+            let invocation = invocation(&id.to_string());
+            assert!(runner
+                .schedule(("on_new_window", Some(id)), invocation, start)
+                .is_some());
+        }
+        assert!(runner
+            .schedule(("on_new_window", Some(1)), invocation("1"), start)
+            .is_none());
+        assert_eq!(runner.take_due(start + COOLDOWN), vec![invocation("1")]);
+        // the windows that were handled long ago are forgotten
+        let later = start + Duration::from_secs(1);
+        assert!(runner
+            .schedule(("on_window_close", Some(2)), invocation("2"), later)
+            .is_some());
+        assert_eq!(runner.last_run.len(), 1);
+    }
+
+    #[test]
+    fn the_environment_should_describe_the_event() {
+        let mut manager = Manager::new_test(vec!["web".to_string(), "chat".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let mut window = Window::new(WindowHandle::MockHandle(5), Some("Firefox".into()), None);
+        window.res_class = Some("firefox".to_string());
+        manager.window_created_handler(window, -1, -1);
+
+        let event = Event::UrgencyChanged {
+            window: 5,
+            urgent: true,
+        };
+        let env = environment(&manager.state, &event);
+        assert!(env.contains(&("LEFTWM_EVENT", "UrgencyChanged".to_string())));
+        assert!(env.contains(&("LEFTWM_WINDOW_ID", "5".to_string())));
+        assert!(env.contains(&("LEFTWM_WINDOW_CLASS", "firefox".to_string())));
+        assert!(env.contains(&("LEFTWM_TAG", "web".to_string())));
+        assert!(env.contains(&("LEFTWM_WORKSPACE", "0".to_string())));
+    }
+}
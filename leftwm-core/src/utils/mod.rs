@@ -1,7 +1,11 @@
 //! Various shared functions that `LeftWM` uses.
 pub mod child_process;
 pub mod command_pipe;
+pub mod command_socket;
+pub mod config_watcher;
 pub mod helpers;
+pub mod hooks;
+pub mod placeholders;
 pub mod state_socket;
 pub mod window_updater;
 pub mod xkeysym_lookup;
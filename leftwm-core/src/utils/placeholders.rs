@@ -0,0 +1,218 @@
+//! Expands placeholders like `{window_class}` in shell commands, from the current state.
+//!
+//! The values are not pasted in the command, as a window could then choose what the shell runs
+//! with its class or title. They are passed in environment variables like
+//! `LEFTWM_FOCUSED_WINDOW_CLASS`, and the placeholders are replaced by a quoted reference to them,
+//! so they are one argument whatever they contain, inside quotes or not. Placeholders without a
+//! value, like `{window_id}` when no window has the focus, are empty. Other braces are left
+//! untouched.
+use crate::models::dto::window_id;
+use crate::state::State;
+use crate::Window;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Processes followed from the focused window to its newest child process, and so on.
+const MAX_PROCESS_DEPTH: usize = 8;
+
+/// `command` with its placeholders replaced by references to variables, and the environment
+/// giving these variables their value in `state`.
+pub fn expand(command: &str, state: &State) -> (String, Vec<(&'static str, String)>) {
+    let mut expanded = String::with_capacity(command.len());
+    let mut env: Vec<(&'static str, String)> = vec![];
+    let mut quote = None;
+    let mut rest = command;
+    while let Some(c) = rest.chars().next() {
+        let placeholder = rest.find('}').filter(|_| c == '{').map(|end| &rest[..=end]);
+        if let Some((placeholder, (variable, value))) =
+            placeholder.and_then(|placeholder| Some((placeholder, value(placeholder, state)?)))
+        {
+            // Double quotes already keep the value in one argument.
+            let reference = ["${", variable, "}"].concat();
+            if quote == Some('"') {
+                expanded.push_str(&reference);
+            } else {
+                expanded.push_str(&["\"", &reference, "\""].concat());
+            }
+            if !env.iter().any(|(name, _)| *name == variable) {
+                env.push((variable, value));
+            }
+            rest = &rest[placeholder.len()..];
+            continue;
+        }
+        expanded.push(c);
+        rest = &rest[c.len_utf8()..];
+        match c {
+            '\\' if quote != Some('\'') => {
+                if let Some(escaped) = rest.chars().next() {
+                    expanded.push(escaped);
+                    rest = &rest[escaped.len_utf8()..];
+                }
+            }
+            '\'' | '"' if quote.is_none() => quote = Some(c),
+            _ if quote == Some(c) => quote = None,
+            _ => {}
+        }
+    }
+    (expanded, env)
+}
+
+/// The variable holding the value of `placeholder` and its value, `None` if it is not a
+/// placeholder.
+fn value(placeholder: &str, state: &State) -> Option<(&'static str, String)> {
+    let window = state.focus_manager.window(&state.windows);
+    let workspace_index = state.focus_manager.workspace_history.front().copied();
+    let tag = state.focus_manager.tag(0);
+    let (variable, value) = match placeholder {
+        "{window_id}" => (
+            "LEFTWM_FOCUSED_WINDOW_ID",
+            window.map(|w| window_id(w.handle).to_string()),
+        ),
+        "{window_pid}" => (
+            "LEFTWM_FOCUSED_WINDOW_PID",
+            window.and_then(|w| w.pid).map(|pid| pid.to_string()),
+        ),
+        "{window_class}" => (
+            "LEFTWM_FOCUSED_WINDOW_CLASS",
+            window.and_then(|w| w.res_class.clone()),
+        ),
+        "{tag}" => ("LEFTWM_FOCUSED_TAG", tag.map(|tag| tag.to_string())),
+        "{tag_label}" => (
+            "LEFTWM_FOCUSED_TAG_LABEL",
+            tag.and_then(|tag| state.tags.get(tag))
+                .map(|tag| tag.label.clone()),
+        ),
+        "{workspace}" => (
+            "LEFTWM_FOCUSED_WORKSPACE",
+            workspace_index.map(|index| index.to_string()),
+        ),
+        "{layout}" => (
+            "LEFTWM_FOCUSED_LAYOUT",
+            workspace_index
+                .and_then(|index| state.workspaces.get(index))
+                .map(|ws| format!("{:?}", ws.layout)),
+        ),
+        "{cwd_of_focused_pid}" => (
+            "LEFTWM_FOCUSED_CWD",
+            window
+                .and_then(focused_cwd)
+                .map(|cwd| cwd.to_string_lossy().into_owned()),
+        ),
+        _ => return None,
+    };
+    Some((variable, value.unwrap_or_default()))
+}
+
+/// The working directory of the process of the window, or of its newest child process as the
+/// shell of a terminal is usually where the user changes directory.
+fn focused_cwd(window: &Window) -> Option<PathBuf> {
+    let mut pid = window.pid?;
+    for _ in 0..MAX_PROCESS_DEPTH {
+        match newest_child(pid) {
+            Some(child) => pid = child,
+            None => break,
+        }
+    }
+    fs::read_link(Path::new("/proc").join(pid.to_string()).join("cwd")).ok()
+}
+
+fn newest_child(pid: u32) -> Option<u32> {
+    let task = Path::new("/proc")
+        .join(pid.to_string())
+        .join("task")
+        .join(pid.to_string());
+    let children = fs::read_to_string(task.join("children")).ok()?;
+    children
+        .split_whitespace()
+        .filter_map(|c| c.parse().ok())
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Screen, WindowHandle};
+    use crate::Manager;
+
+    /// What `sh` prints running the expanded `command`.
+    fn run(command: &str, state: &State) -> String {
+        let (command, env) = expand(command, state);
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .envs(env)
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    #[test]
+    fn placeholders_should_be_replaced_by_references_to_their_value() {
+        let mut manager = Manager::new_test(vec!["web".to_string(), "chat".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(5), None, None),
+            -1,
+            -1,
+        );
+
+        let (command, env) = expand(
+            "notify {window_id} {tag}:{tag_label} {workspace} {window_id}",
+            &manager.state,
+        );
+        assert_eq!(
+            command,
+            "notify \"${LEFTWM_FOCUSED_WINDOW_ID}\" \"${LEFTWM_FOCUSED_TAG}\":\"${LEFTWM_FOCUSED_TAG_LABEL}\" \"${LEFTWM_FOCUSED_WORKSPACE}\" \"${LEFTWM_FOCUSED_WINDOW_ID}\""
+        );
+        assert_eq!(
+            env,
+            vec![
+                ("LEFTWM_FOCUSED_WINDOW_ID", "5".to_string()),
+                ("LEFTWM_FOCUSED_TAG", "1".to_string()),
+                ("LEFTWM_FOCUSED_TAG_LABEL", "web".to_string()),
+                ("LEFTWM_FOCUSED_WORKSPACE", "0".to_string()),
+            ]
+        );
+        assert_eq!(
+            expand("cd {cwd_of_focused_pid} && ls {a,b} {", &manager.state).0,
+            "cd \"${LEFTWM_FOCUSED_CWD}\" && ls {a,b} {"
+        );
+    }
+
+    #[test]
+    fn windows_should_not_be_able_to_choose_what_the_shell_runs() {
+        let mut manager = Manager::new_test(vec!["web".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let class = r#"x'"$(echo pwned)`echo pwned`\"#;
+        let mut window = Window::new(WindowHandle::MockHandle(5), None, None);
+        window.res_class = Some(class.to_string());
+        manager.window_created_handler(window, -1, -1);
+
+        let state = &manager.state;
+        assert_eq!(run("printf %s {window_class}", state), class);
+        assert_eq!(
+            run(r#"printf %s "Focused {window_class}""#, state),
+            ["Focused ", class].concat()
+        );
+        assert_eq!(run("sh -c 'printf %s {window_class}'", state), class);
+        assert_eq!(
+            run(r#"printf %s "\"{window_class}\"" 'it'\''s'"#, state),
+            ["\"", class, "\"it's"].concat()
+        );
+    }
+
+    #[test]
+    fn the_cwd_should_be_the_one_of_the_process() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("10")
+            .current_dir("/")
+            .spawn()
+            .unwrap();
+        let mut window = Window::new(WindowHandle::MockHandle(1), None, None);
+        window.pid = Some(child.id());
+        let cwd = focused_cwd(&window);
+        child.kill().ok();
+        child.wait().ok();
+        assert_eq!(cwd, Some(PathBuf::from("/")));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::config::Config;
 use crate::display_servers::DisplayServer;
-use crate::models::{Manager, Tag};
+use crate::models::{Manager, Tag, Window, WindowType};
 
 impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     /*
@@ -25,6 +25,20 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             for tag in &tags {
                 tag.update_windows(windows, ws);
             }
+            if !ws.shows_docks() {
+                windows
+                    .iter_mut()
+                    .filter(|w| w.r#type == WindowType::Dock && ws.is_displaying(w))
+                    .for_each(|w| w.set_visible(false));
+            }
+            if self.state.honour_size_hints {
+                windows
+                    .iter_mut()
+                    .filter(|w| {
+                        ws.is_managed(w) && !w.floating() && !w.is_fullscreen() && w.visible()
+                    })
+                    .for_each(Window::apply_size_hints);
+            }
         }
 
         self.state
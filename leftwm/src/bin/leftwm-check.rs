@@ -1,10 +1,10 @@
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use clap::{App, Arg};
-use leftwm::{Config, Keybind, ThemeSetting};
-use leftwm_core::config::Workspace;
-use leftwm_core::utils;
-use std::collections::HashMap;
-use std::convert::TryFrom;
+use leftwm::check::{
+    check_aliases, check_environment, check_keybinds, check_simulation, check_theme_toml_contents,
+    check_workspace_ids, missing_expected_file, Report,
+};
+use leftwm::{migration, Config, ConfigFormat, Keybind};
 use std::fs;
 use std::os::unix::fs::PermissionsExt;
 use std::path::PathBuf;
@@ -28,292 +28,238 @@ async fn main() -> Result<()> {
                 .long("verbose")
                 .help("Outputs received configuration file."),
         )
+        .arg(
+            Arg::with_name("migrate")
+                .long("migrate")
+                .help("Migrates config.toml to the current version, keeping a backup of it."),
+        )
+        .arg(
+            Arg::with_name("simulate")
+                .long("simulate")
+                .help("Runs the command of every keybind in a simulated LeftWM, without X."),
+        )
+        .arg(
+            Arg::with_name("json")
+                .long("json")
+                .help("Prints the problems found as JSON, for editor integration."),
+        )
         .get_matches();
 
     let config_file = matches.value_of("INPUT");
     let verbose = matches.occurrences_of("verbose") >= 1;
+    let mut report = Report::new(matches.is_present("json"), verbose);
 
-    println!(
+    report.info(format!(
         "\x1b[0;94m::\x1b[0m LeftWM version: {}",
         env!("CARGO_PKG_VERSION")
-    );
-    println!(
+    ));
+    report.info(format!(
         "\x1b[0;94m::\x1b[0m LeftWM git hash: {}",
         git_version::git_version!(fallback = "NONE")
-    );
-    println!("\x1b[0;94m::\x1b[0m Loading configuration . . .");
-    match load_from_file(config_file, verbose) {
-        Ok(config) => {
-            println!("\x1b[0;92m    -> Configuration loaded OK \x1b[0m");
+    ));
+    if matches.is_present("migrate") {
+        report.section("Migrating configuration");
+        if let Err(e) = migrate_config(config_file, &report) {
+            report.error("config", format!("Migration failed. Reason: {:?}", e));
+        }
+    } else {
+        check_config_version(config_file, &mut report);
+    }
+    report.section("Loading configuration");
+    match load_from_file(config_file, &report) {
+        Ok((config, toml_keybinds)) => {
+            report.ok("Configuration loaded OK");
             if verbose {
                 dbg!(&config);
             }
-            check_workspace_ids(config.workspaces, verbose);
-            check_keybinds(config.keybind, verbose);
+            check_workspace_ids(config.workspaces.clone(), &mut report);
+            check_keybinds(&config, toml_keybinds, &mut report);
+            check_aliases(&config, &mut report);
+            if matches.is_present("simulate") {
+                check_simulation(&config, &mut report);
+            }
         }
         Err(e) => {
-            println!("Configuration failed. Reason: {:?}", e);
+            report.error("config", format!("Configuration failed. Reason: {:?}", e));
         }
     }
-    println!("\x1b[0;94m::\x1b[0m Checking environment . . .");
-    check_elogind(verbose)?;
-    println!("\x1b[0;94m::\x1b[0m Checking theme . . .");
-    check_theme(verbose);
+    report.section("Checking environment");
+    check_environment(
+        std::env::var("XDG_RUNTIME_DIR"),
+        leftwm::is_program_in_path("loginctl"),
+        &mut report,
+    );
+    report.section("Checking theme");
+    check_theme(&mut report);
 
+    if !report.finish() {
+        std::process::exit(1);
+    }
     Ok(())
 }
 
 /// Loads configuration from either specified file (preferred) or default.
+///
+/// The format is picked by the file extension. For `config.toml`, the untyped keybinds are
+/// returned as well so their commands can be checked.
 /// # Errors
 ///
 /// Errors if file cannot be read. Indicates filesystem error
 /// (inadequate permissions, disk full, etc.)
 /// If a path is specified and does not exist, returns `LeftError`.
-pub fn load_from_file(fspath: Option<&str>, verbose: bool) -> Result<Config> {
+fn load_from_file(fspath: Option<&str>, report: &Report) -> Result<(Config, Vec<Keybind>)> {
     let config_filename = match fspath {
         Some(fspath) => {
-            println!("\x1b[1;35mNote: Using file {} \x1b[0m", fspath);
+            report.info(format!("\x1b[1;35mNote: Using file {} \x1b[0m", fspath));
             PathBuf::from(fspath)
         }
 
-        None => BaseDirectories::with_prefix("leftwm")?.place_config_file("config.toml")?,
+        None => leftwm::config_file()?,
     };
-    if verbose {
+    if report.verbose {
         dbg!(&config_filename);
     }
-    let contents = fs::read_to_string(config_filename)?;
-    if verbose {
-        dbg!(&contents);
+    let format = ConfigFormat::from_path(&config_filename).with_context(|| {
+        format!(
+            "Unknown config format for {}, expected .toml, .ron, .json or .yaml",
+            config_filename.display()
+        )
+    })?;
+    if report.verbose {
+        dbg!(fs::read_to_string(&config_filename)?);
     }
-    Ok(toml::from_str(&contents)?)
+    let toml_keybinds = match format {
+        ConfigFormat::Toml => leftwm::read_toml(&config_filename)?.keybind,
+        _ => vec![],
+    };
+    Ok((format.read(&config_filename)?, toml_keybinds))
 }
 
-/// Checks defined workspaces to ensure no ID collisions occur.
-fn check_workspace_ids(workspaces: Option<Vec<Workspace>>, verbose: bool) -> bool {
-    workspaces.map_or(true, |wss|
-    {
-        if verbose {
-            println!("Checking config for valid workspace definitions.");
-        }
-        let ids = leftwm::get_workspace_ids(&wss);
-        if ids.iter().any(std::option::Option::is_some) {
-            if !leftwm::all_ids_some(&ids)
-            {
-                println!("Your config.toml specifies an ID for some but not all workspaces. This can lead to ID collisions and is not allowed. The default config will be used instead.");
-                false
-            } else if leftwm::all_ids_unique(&ids) {
-                true
-            } else {
-                println!("Your config.toml contains duplicate workspace IDs. Please assign unique IDs to workspaces. The default config will be used instead.");
-                false
-            }
-        } else {
-            true
-        }
+/// Returns the `config.toml` and the files it includes, or nothing for other config formats.
+fn toml_files(fspath: Option<&str>) -> Result<Vec<PathBuf>> {
+    let config_filename = match fspath {
+        Some(fspath) => PathBuf::from(fspath),
+        None => leftwm::config_file()?,
+    };
+    if ConfigFormat::from_path(&config_filename) != Some(ConfigFormat::Toml) {
+        return Ok(vec![]);
     }
-    )
+    let mut files = leftwm::toml_includes(&config_filename)?;
+    files.insert(0, config_filename);
+    Ok(files)
 }
 
-/// Check all keybinds to ensure that required values are provided
-/// Checks to see if value is provided (if required)
-/// Checks to see if keys are valid against Xkeysym
-/// Ideally, we will pass this to the command handler with a dummy config
-fn check_keybinds(keybinds: Vec<Keybind>, verbose: bool) -> bool {
-    let mut returns = Vec::new();
-    println!("\x1b[0;94m::\x1b[0m Checking keybinds . . .");
-    let mut bindings = HashMap::new();
-    for keybind in keybinds {
-        if verbose {
-            println!("Keybind: {:?} {}", keybind, keybind.value.is_none());
-        }
-        if let Err(err) = leftwm_core::Keybind::try_from(keybind.clone()) {
-            returns.push((Some(keybind.clone()), err.to_string()));
-        }
-        if utils::xkeysym_lookup::into_keysym(&keybind.key).is_none() {
-            returns.push((
-                Some(keybind.clone()),
-                format!("Key `{}` is not valid", keybind.key),
-            ));
-        }
-
-        for m in &keybind.modifier {
-            if m != "modkey" && m != "mousekey" && utils::xkeysym_lookup::into_mod(m) == 0 {
-                returns.push((
-                    Some(keybind.clone()),
-                    format!("Modifier `{}` is not valid", m),
-                ));
+/// Warns about deprecated settings that `--migrate` would rewrite.
+fn check_config_version(fspath: Option<&str>, report: &mut Report) {
+    for file in toml_files(fspath).unwrap_or_default() {
+        let migrated = match fs::read_to_string(&file).map(|contents| migration::migrate(&contents))
+        {
+            Ok(Ok(migrated)) => migrated,
+            Ok(Err(e)) => {
+                report.error("config", format!("{} for {}", e, file.display()));
+                continue;
             }
-        }
-        let mut modkey = keybind.modifier.clone();
-        modkey.sort();
-        if let Some(conflict_key) = bindings.get(&(modkey.clone(), keybind.key.clone())) {
-            returns.push((
-                None,
+            Err(_) => continue,
+        };
+        if !migrated.changes.is_empty() {
+            report.warn(
+                "config",
                 format!(
-                    "\x1b[0m\x1b[1mMultiple commands bound to key combination {} + {}:\
-                    \n\x1b[1;91m    -> {:?}\
-                    \n    -> {:?}\
-                    \n\x1b[0mHelp: change one of the keybindings to something else.\n",
-                    keybind.modifier.join(" + "),
-                    keybind.key,
-                    conflict_key,
-                    keybind.command,
+                    "{} uses deprecated settings, run `leftwm-check --migrate` to update it: {}",
+                    file.display(),
+                    migrated.changes.join(", ")
                 ),
-            ));
-        } else {
-            bindings.insert((modkey, keybind.key), keybind.command);
-        }
-    }
-    if returns.is_empty() {
-        println!("\x1b[0;92m    -> All keybinds OK\x1b[0m");
-        true
-    } else {
-        for error in returns {
-            match error.0 {
-                Some(binding) => {
-                    println!(
-                        "\x1b[1;91mERROR: {} for keybind {:?}\x1b[0m",
-                        error.1, binding
-                    );
-                }
-                None => {
-                    println!("\x1b[1;91mERROR: {} \x1b[0m", error.1);
-                }
-            }
+            );
         }
-        false
     }
 }
 
-fn check_elogind(verbose: bool) -> Result<()> {
-    // We assume that if it is in the path it's all good
-    // We also cross-reference the ENV variable
-    match (
-        std::env::var("XDG_RUNTIME_DIR"),
-        leftwm::is_program_in_path("loginctl"),
-    ) {
-        (Ok(val), true) => {
-            if verbose {
-                println!(":: XDG_RUNTIME_DIR: {}, LOGINCTL OKAY", val);
-            }
-
-            println!("\x1b[0;92m    -> Environment OK \x1b[0;92m");
-
-            Ok(())
-        }
-        (Ok(val), false) => {
-            if verbose {
-                println!(":: XDG_RUNTIME_DIR: {}, LOGINCTL not installed", val);
-            }
-
-            println!("\x1b[0;92m    -> Environment OK (has XDG_RUNTIME_DIR) \x1b[0;92m");
-
-            Ok(())
-        }
-        (Err(e), false) => {
-            if verbose {
-                println!(":: XDG_RUNTIME_DIR_ERROR: {:?}, LOGINCTL BAD", e);
-            }
-
-            bail!(
-                "Elogind not installed/operating and no alternative XDG_RUNTIME_DIR is set. \
-                See https://github.com/leftwm/leftwm/wiki/XDG_RUNTIME_DIR for more information."
-            );
+/// Migrates `config.toml` and the files it includes to the current version.
+///
+/// Each migrated file is backed up next to it, like `config.toml.v0.bak` for version 0.
+fn migrate_config(fspath: Option<&str>, report: &Report) -> Result<()> {
+    for file in toml_files(fspath)? {
+        let contents = fs::read_to_string(&file)?;
+        let migrated = migration::migrate(&contents)
+            .with_context(|| format!("cannot migrate {}", file.display()))?;
+        if migrated.from_version == migration::CONFIG_VERSION {
+            report.ok(&format!("{} is up to date", file.display()));
+            continue;
         }
-        (Err(e), true) => {
-            if verbose {
-                println!(":: XDG_RUNTIME_DIR: {:?}, LOGINCTL OKAY", e);
-            }
-            println!(
-                "\x1b[1;93mWARN: Elogind/systemd installed but XDG_RUNTIME_DIR not set.\nThis may be because elogind isn't started. \x1b[0m",
-            );
-            Ok(())
+        let mut backup = file.clone().into_os_string();
+        backup.push(format!(".v{}.bak", migrated.from_version));
+        fs::copy(&file, &backup)?;
+        fs::write(&file, migrated.contents)?;
+        report.ok(&format!(
+            "Migrated {} from version {} to {}, the original is kept as {}",
+            file.display(),
+            migrated.from_version,
+            migration::CONFIG_VERSION,
+            PathBuf::from(backup).display()
+        ));
+        for change in migrated.changes {
+            report.info(format!("    -> {}", change));
         }
     }
+    Ok(())
 }
 
 /// Checks if `.config/leftwm/theme/current/` is a valid path
 /// Checks if `up` and `down` scripts are in the `current` directory and have executable permission
-/// Checks if `theme.toml` is in the `current` path
-fn check_theme(verbose: bool) -> bool {
-    let xdg_base_dir = BaseDirectories::with_prefix("leftwm/themes");
-    let err_formatter = |s| println!("\x1b[1;91mERROR:\x1b[0m\x1b[1m {} \x1b[0m", s);
-
-    if let Err(e) = xdg_base_dir {
-        err_formatter(e.to_string());
-        return false;
-    }
-
-    let xdg_base_dir = xdg_base_dir.unwrap();
+/// Checks if `theme.toml` is in the `current` path and is a valid theme
+fn check_theme(report: &mut Report) {
+    let xdg_base_dir = match BaseDirectories::with_prefix("leftwm/themes") {
+        Ok(xdg_base_dir) => xdg_base_dir,
+        Err(e) => return report.error("theme", e),
+    };
     let path_current_theme = xdg_base_dir.find_config_file("current");
 
-    match check_current_theme_set(&path_current_theme, verbose) {
-        Ok(_) => check_theme_contents(xdg_base_dir.list_config_files("current"), verbose),
-        Err(e) => {
-            err_formatter(e.to_string());
-            false
-        }
+    match check_current_theme_set(&path_current_theme, report) {
+        Ok(_) => check_theme_contents(xdg_base_dir.list_config_files("current"), report),
+        Err(e) => report.error("theme", e),
     }
 }
 
-fn check_theme_contents(filepaths: Vec<PathBuf>, verbose: bool) -> bool {
-    let mut returns = Vec::new();
-    let missing_files = missing_expected_file(&filepaths);
-
-    if !missing_files.is_empty() {
-        missing_files
-            .into_iter()
-            .for_each(|file| returns.push(format!("File not found: {}", file)));
+fn check_theme_contents(filepaths: Vec<PathBuf>, report: &mut Report) {
+    for file in missing_expected_file(&filepaths) {
+        report.error("theme", format!("File not found: {}", file));
     }
 
     for filepath in filepaths {
-        match filepath {
-            f if f.ends_with("up") => match check_permissions(f, verbose) {
-                Ok(_fp) => continue,
-                Err(e) => returns.push(e.to_string()),
-            },
-            f if f.ends_with("down") => match check_permissions(f, verbose) {
-                Ok(_fp) => continue,
-                Err(e) => returns.push(e.to_string()),
-            },
-            f if f.ends_with("theme.toml") => match check_theme_toml(f, verbose) {
-                Ok(_fp) => continue,
-                Err(e) => returns.push(e.to_string()),
-            },
+        let checked = match filepath {
+            f if f.ends_with("up") || f.ends_with("down") => check_permissions(f, report),
+            f if f.ends_with("theme.toml") => check_theme_toml(f, report),
             _ => continue,
+        };
+        if let Err(e) = checked {
+            report.error("theme", e);
         }
     }
 
-    if returns.is_empty() {
-        println!("\x1b[0;92m    -> Theme OK \x1b[0;92m");
-        true
-    } else {
-        for error in &returns {
-            println!("\x1b[1;91mERROR:\x1b[0m\x1b[1m {} \x1b[0m", error);
-        }
-        false
+    if report.errors("theme") == 0 {
+        report.ok("Theme OK");
     }
 }
 
-fn missing_expected_file<'a>(filepaths: &[PathBuf]) -> Vec<&'a str> {
-    vec!["up", "down", "theme.toml"]
-        .into_iter()
-        .filter(|f| !filepaths.iter().any(|fp| fp.ends_with(f)))
-        .collect()
-}
-
-fn check_current_theme_set(filepath: &Option<PathBuf>, verbose: bool) -> Result<&PathBuf> {
+fn check_current_theme_set<'a>(
+    filepath: &'a Option<PathBuf>,
+    report: &mut Report,
+) -> Result<&'a PathBuf> {
     match &filepath {
         Some(p) => {
-            if verbose {
-                if fs::symlink_metadata(&p)?.file_type().is_symlink() {
-                    println!(
-                        "Found symlink `current`, pointing to theme folder: {:?}",
-                        fs::read_link(&p).unwrap()
-                    );
-                } else {
-                    println!("\x1b[1;93mWARN: Found `current` theme folder: {:?}. Use of a symlink is recommended, instead.\x1b[0m", p);
-                }
+            if fs::symlink_metadata(&p)?.file_type().is_symlink() {
+                report.detail(format!(
+                    "Found symlink `current`, pointing to theme folder: {:?}",
+                    fs::read_link(&p)?
+                ));
+            } else {
+                report.warn(
+                    "theme",
+                    format!(
+                        "Found `current` theme folder: {:?}. Use of a symlink is recommended, instead.",
+                        p
+                    ),
+                );
             }
             Ok(p)
         }
@@ -321,17 +267,15 @@ fn check_current_theme_set(filepath: &Option<PathBuf>, verbose: bool) -> Result<
     }
 }
 
-fn check_permissions(filepath: PathBuf, verbose: bool) -> Result<PathBuf> {
+fn check_permissions(filepath: PathBuf, report: &Report) -> Result<PathBuf> {
     let metadata = fs::metadata(&filepath)?;
     let permissions = metadata.permissions();
     if metadata.is_file() && (permissions.mode() & 0o111 != 0) {
-        if verbose {
-            println!(
-                "Found `{}` with executable permissions: {:?}",
-                filepath.display(),
-                permissions.mode() & 0o111 != 0,
-            );
-        }
+        report.detail(format!(
+            "Found `{}` with executable permissions: {:?}",
+            filepath.display(),
+            permissions.mode() & 0o111 != 0,
+        ));
 
         Ok(filepath)
     } else {
@@ -342,24 +286,15 @@ fn check_permissions(filepath: PathBuf, verbose: bool) -> Result<PathBuf> {
     }
 }
 
-fn check_theme_toml(filepath: PathBuf, verbose: bool) -> Result<PathBuf> {
+fn check_theme_toml(filepath: PathBuf, report: &Report) -> Result<PathBuf> {
     let metadata = fs::metadata(&filepath)?;
     let contents = fs::read_to_string(&filepath.as_path())?;
 
     if metadata.is_file() {
-        if verbose {
-            println!("Found: {}", filepath.display());
-        }
-
-        match toml::from_str::<ThemeSetting>(&contents) {
-            Ok(_) => {
-                if verbose {
-                    println!("The theme file looks OK.");
-                }
-                Ok(filepath)
-            }
-            Err(err) => bail!("Could not parse theme file: {}", err),
-        }
+        report.detail(format!("Found: {}", filepath.display()));
+        check_theme_toml_contents(&contents)?;
+        report.detail("The theme file looks OK.");
+        Ok(filepath)
     } else {
         bail!("No `theme.toml` found at path: {}", filepath.display());
     }
@@ -1,7 +1,10 @@
 use clap::{App, Arg};
-use leftwm_core::errors::Result;
-use std::fs::OpenOptions;
+use leftwm_core::utils::command_socket::{Reply, Request};
+use leftwm_core::{errors::Result, Command};
+use std::fs::{File, OpenOptions};
 use std::io::prelude::*;
+use std::io::BufReader;
+use std::os::unix::net::UnixStream;
 use xdg::BaseDirectories;
 
 #[tokio::main]
@@ -24,17 +27,45 @@ async fn main() -> Result<()> {
         )
         .get_matches();
 
-    let file_path = BaseDirectories::with_prefix("leftwm")?
-        .find_runtime_file("commands.pipe")
-        .expect("ERROR: Couldn't find commands.pipe");
-    let mut file = OpenOptions::new()
-        .append(true)
-        .open(file_path)
-        .expect("ERROR: Couldn't open commands.pipe");
+    let mut failed = false;
     if let Some(commands) = matches.values_of("command") {
+        let base_dirs = BaseDirectories::with_prefix("leftwm")?;
+        let socket = base_dirs
+            .find_runtime_file("commands.sock")
+            .and_then(|socket_file| match UnixStream::connect(&socket_file) {
+                Ok(stream) => Some(stream),
+                // A socket left by a LeftWM that didn't exit cleanly.
+                Err(e) => {
+                    eprintln!(
+                        " WARNING: Couldn't connect to {}: {}",
+                        socket_file.display(),
+                        e
+                    );
+                    None
+                }
+            });
+        // LeftWM versions without the socket only read commands from the pipe.
+        let pipe = || {
+            let pipe_file = base_dirs.find_runtime_file("commands.pipe")?;
+            OpenOptions::new().append(true).open(pipe_file).ok()
+        };
+        let mut sender = if let Some(stream) = socket {
+            Sender::Socket(stream)
+        } else if let Some(file) = pipe() {
+            Sender::Pipe(file)
+        } else {
+            eprintln!(" ERROR: Couldn't connect to LeftWM, is it running?");
+            std::process::exit(1);
+        };
         for command in commands {
-            if let Err(e) = writeln!(file, "{}", command) {
-                eprintln!(" ERROR: Couldn't write to commands.pipe: {}", e);
+            if let Err(e) = command.parse::<Command>() {
+                eprintln!(" ERROR: Invalid command `{}`: {}", command, e);
+                failed = true;
+                continue;
+            }
+            if let Err(e) = sender.send(command) {
+                eprintln!(" ERROR: `{}` failed: {}", command, e);
+                failed = true;
             }
         }
     }
@@ -51,6 +82,9 @@ async fn main() -> Result<()> {
         UnloadTheme
         SoftReload
         ToggleFullScreen
+        ToggleAbove
+        ToggleBelow
+        ToggleMaximize
         SwapScreens
         MoveWindowToLastWorkspace
         FloatingToTile
@@ -69,10 +103,18 @@ async fn main() -> Result<()> {
         PreviousLayout
         RotateTag
         CloseWindow
+        CenterFloating
+        MergeWorkspace
+        ToggleStruts
+        ToggleGutters
+        ToggleDocks
+        ZenMode
 
         Commands with arguments:
             Use quotations for the command and arguments, like this:
             leftwm-command \"<command> <args>\"
+            Arguments containing spaces can be quoted again, like this:
+            leftwm-command \"ToggleScratchPad 'Music Player'\"
 
         LoadTheme              Args: <Path_to/theme.toml> 
         ToggleScratchPad       Args: <ScratchpadName>
@@ -80,11 +122,54 @@ async fn main() -> Result<()> {
         SendWindowToTag        Args: <tag_index> (int)
         SetLayout              Args: <LayoutName>
         SetMarginMultiplier    Args: <multiplier-value> (float)
+        MoveFloating           Args: <dx> <dy> (int)
+        ResizeFloating         Args: <dw> <dh> (int)
+        SnapFloating           Args: <TopLeft|TopRight|BottomLeft|BottomRight|Center|
+                                      LeftHalf|RightHalf|TopHalf|BottomHalf>
+        SplitWorkspace         Args: <Horizontal|Vertical> <ratio> (percent)
+        ResizeWorkspaceSplit   Args: <delta> (percent)
+        Sequence               Args: <command>... (each quoted), stops at the first failure
+        Alias                  Args: <AliasName> (defined in [alias])
         
         For more information please visit:
         https://github.com/leftwm/leftwm/wiki/External-Commands
          "
         );
     }
+    if failed {
+        std::process::exit(1);
+    }
     Ok(())
 }
+
+/// Where commands are written to.
+enum Sender {
+    /// `commands.sock`, which replies with the outcome of each command.
+    Socket(UnixStream),
+    Pipe(File),
+}
+
+impl Sender {
+    /// Send a command, returning why it failed.
+    fn send(&mut self, command: &str) -> std::result::Result<(), String> {
+        match self {
+            Self::Socket(stream) => {
+                let request = Request::Command(command.to_string());
+                let mut line = serde_json::to_string(&request).map_err(|e| e.to_string())?;
+                line.push('\n');
+                stream
+                    .write_all(line.as_bytes())
+                    .map_err(|e| e.to_string())?;
+                let mut reply = String::new();
+                BufReader::new(&*stream)
+                    .read_line(&mut reply)
+                    .map_err(|e| e.to_string())?;
+                match serde_json::from_str(&reply).map_err(|e| e.to_string())? {
+                    Reply::Success { .. } => Ok(()),
+                    Reply::Failure { reason } | Reply::ParseError { reason } => Err(reason),
+                }
+            }
+            Self::Pipe(file) => writeln!(file, "{}", command).map_err(|e| e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use anyhow::{bail, Context, Result};
+use clap::{App, Arg};
+use leftwm::{Config, ConfigFormat, Keybind};
+use std::convert::TryFrom;
+use std::fs;
+use std::path::PathBuf;
+use xdg::BaseDirectories;
+
+fn main() -> Result<()> {
+    let matches = App::new("LeftWM Convert")
+        .author("Lex Childs <lex.childs@gmail.com>")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("converts config.toml into a format with typed keybinds")
+        .arg(
+            Arg::with_name("format")
+                .help("The format to convert to.")
+                .possible_values(&["ron", "json", "yaml"])
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("INPUT")
+                .short("i")
+                .long("input")
+                .takes_value(true)
+                .help(
+                    "The config.toml to convert. Uses the one in the config directory otherwise.",
+                ),
+        )
+        .arg(
+            Arg::with_name("force")
+                .short("f")
+                .long("force")
+                .help("Overwrites the converted config if it already exists."),
+        )
+        .get_matches();
+
+    let input = match matches.value_of("INPUT") {
+        Some(input) => PathBuf::from(input),
+        None => BaseDirectories::with_prefix("leftwm")?
+            .find_config_file("config.toml")
+            .context("No config.toml found")?,
+    };
+    let format = ConfigFormat::from_path(&PathBuf::from(format!(
+        "config.{}",
+        matches.value_of("format").unwrap_or_default()
+    )))
+    .context("Unknown config format")?;
+    let output = input.with_extension(format.extension());
+    if output.exists() && !matches.is_present("force") {
+        bail!(
+            "{} already exists, use --force to overwrite it",
+            output.display()
+        );
+    }
+
+    let config: Config<Keybind> = leftwm::read_toml(&input)?;
+    for keybind in &config.keybind {
+        if let Err(err) = leftwm_core::Keybind::try_from(keybind.clone()) {
+            eprintln!("Dropping invalid keybind {:?}: {}", keybind, err);
+        }
+    }
+    let config: Config = config.into();
+    fs::write(&output, format.serialize(&config)?)?;
+    println!(
+        "Wrote {}. It takes precedence over {}, which can now be removed.",
+        output.display(),
+        input.display()
+    );
+    Ok(())
+}
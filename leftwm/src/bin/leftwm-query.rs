@@ -0,0 +1,78 @@
+use clap::{App, Arg};
+use leftwm_core::utils::command_socket::{Reply, Request};
+use std::io::prelude::*;
+use std::io::BufReader;
+use std::os::unix::net::UnixStream;
+use xdg::BaseDirectories;
+
+fn main() {
+    let matches = App::new("LeftWM Query")
+        .author("Lex Childs <lex.childs@gmail.com>")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("Prints information about the windows, workspaces and tags of LeftWM as JSON")
+        .arg(
+            Arg::with_name("query")
+                .help("What to print")
+                .required(true)
+                .possible_values(&[
+                    "windows",
+                    "workspaces",
+                    "tags",
+                    "focused",
+                    "keybinds",
+                    "layouts",
+                ]),
+        )
+        .arg(
+            Arg::with_name("pretty")
+                .short("p")
+                .long("pretty")
+                .help("Indents the JSON"),
+        )
+        .get_matches();
+
+    let request = match matches.value_of("query") {
+        Some("windows") => Request::GetWindows,
+        Some("workspaces") => Request::GetWorkspaces,
+        Some("tags") => Request::GetTags,
+        Some("focused") => Request::GetFocused,
+        Some("keybinds") => Request::GetKeybinds,
+        _ => Request::GetLayouts,
+    };
+    match query(&request) {
+        Ok(data) => {
+            let json = if matches.is_present("pretty") {
+                serde_json::to_string_pretty(&data)
+            } else {
+                serde_json::to_string(&data)
+            };
+            println!("{}", json.unwrap_or_default());
+        }
+        Err(e) => {
+            eprintln!(" ERROR: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Sends the request to `commands.sock`, returning the answer or why there is none.
+fn query(request: &Request) -> Result<serde_json::Value, String> {
+    let socket_file = BaseDirectories::with_prefix("leftwm")
+        .map_err(|e| e.to_string())?
+        .find_runtime_file("commands.sock")
+        .ok_or("Couldn't find commands.sock, is LeftWM running?")?;
+    let mut stream = UnixStream::connect(socket_file).map_err(|e| e.to_string())?;
+    let mut line = serde_json::to_string(request).map_err(|e| e.to_string())?;
+    line.push('\n');
+    stream
+        .write_all(line.as_bytes())
+        .map_err(|e| e.to_string())?;
+    let mut reply = String::new();
+    BufReader::new(stream)
+        .read_line(&mut reply)
+        .map_err(|e| e.to_string())?;
+    match serde_json::from_str(&reply).map_err(|e| e.to_string())? {
+        Reply::Success { data } => Ok(data.unwrap_or_default()),
+        Reply::Failure { reason } | Reply::ParseError { reason } => Err(reason),
+    }
+}
@@ -1,7 +1,7 @@
 //! Starts leftwm programs.
 //!
 //! If no arguments are passed, starts `leftwm-worker`. If arguments are passed, starts
-//! `leftwm-{check, command, state, theme}` as specified, and passes along any extra arguments.
+//! `leftwm-{check, command, convert, query, state, theme}` as specified, and passes along any extra arguments.
 
 use clap::{crate_version, App, AppSettings, SubCommand};
 use leftwm_core::child_process::{self, Nanny};
@@ -19,6 +19,11 @@ fn main() {
     // This is a complete list of accepted subcommands. To add a new one, add a new `insert()` here.
     subcommands.insert("check", "Check syntax of the configuration file");
     subcommands.insert("command", "Send external commands to LeftWM");
+    subcommands.insert("convert", "Convert config.toml to RON, JSON or YAML");
+    subcommands.insert(
+        "query",
+        "Print the windows, workspaces and tags of LeftWM as JSON",
+    );
     subcommands.insert("state", "Print the current state of LeftWM");
     subcommands.insert("theme", "Manage LeftWM themes");
 
@@ -0,0 +1,653 @@
+//! The checks of `leftwm-check`, reporting the problems of a config as diagnostics.
+use crate::{Config, Keybind, ThemeSetting};
+use anyhow::{bail, Result};
+use leftwm_core::config::Workspace;
+use leftwm_core::models::Margins;
+use leftwm_core::{simulation, utils, Command};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::env::VarError;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+/// A problem found by one of the checks.
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    severity: Severity,
+    /// The check that found the problem, like `keybinds` or `theme`.
+    check: &'static str,
+    message: String,
+    /// The key combination of the keybind with the problem, like `modkey + Shift + q`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keybind: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Collects the diagnostics of the checks.
+///
+/// They are printed as they are found, or all at once as JSON with `--json`, in which case
+/// nothing else is written to stdout.
+pub struct Report {
+    pub json: bool,
+    pub verbose: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Report {
+    #[must_use]
+    pub const fn new(json: bool, verbose: bool) -> Self {
+        Self {
+            json,
+            verbose,
+            diagnostics: vec![],
+        }
+    }
+
+    pub fn section(&self, title: &str) {
+        self.info(format!("\x1b[0;94m::\x1b[0m {} . . .", title));
+    }
+
+    pub fn ok(&self, message: &str) {
+        self.info(format!("\x1b[0;92m    -> {} \x1b[0m", message));
+    }
+
+    pub fn info(&self, message: impl Display) {
+        if !self.json {
+            println!("{}", message);
+        } else if self.verbose {
+            eprintln!("{}", message);
+        }
+    }
+
+    /// Prints only with `--verbose`.
+    pub fn detail(&self, message: impl Display) {
+        if self.verbose {
+            self.info(message);
+        }
+    }
+
+    pub fn error(&mut self, check: &'static str, message: impl Display) {
+        self.push(Severity::Error, check, message.to_string(), None);
+    }
+
+    pub fn warn(&mut self, check: &'static str, message: impl Display) {
+        self.push(Severity::Warning, check, message.to_string(), None);
+    }
+
+    pub fn keybind_warning(&mut self, keybind: String, message: impl Display) {
+        self.push(
+            Severity::Warning,
+            "keybinds",
+            message.to_string(),
+            Some(keybind),
+        );
+    }
+
+    pub fn keybind_error(&mut self, keybind: String, message: impl Display) {
+        self.push(
+            Severity::Error,
+            "keybinds",
+            message.to_string(),
+            Some(keybind),
+        );
+    }
+
+    fn push(
+        &mut self,
+        severity: Severity,
+        check: &'static str,
+        message: String,
+        keybind: Option<String>,
+    ) {
+        if !self.json {
+            match (severity, &keybind) {
+                (Severity::Error, Some(keybind)) => println!(
+                    "\x1b[1;91mERROR: {} for keybind {}\x1b[0m",
+                    message, keybind
+                ),
+                (Severity::Error, None) => println!("\x1b[1;91mERROR: {} \x1b[0m", message),
+                (Severity::Warning, Some(keybind)) => {
+                    println!("\x1b[1;93mWARN: {} for keybind {}\x1b[0m", message, keybind);
+                }
+                (Severity::Warning, None) => println!("\x1b[1;93mWARN: {}\x1b[0m", message),
+            }
+        }
+        self.diagnostics.push(Diagnostic {
+            severity,
+            check,
+            message,
+            keybind,
+        });
+    }
+
+    /// Number of errors found by a check so far.
+    #[must_use]
+    pub fn errors(&self, check: &str) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error && d.check == check)
+            .count()
+    }
+
+    /// Prints the JSON report if asked for, returning whether no errors were found.
+    #[must_use]
+    pub fn finish(self) -> bool {
+        let ok = !self
+            .diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error);
+        if self.json {
+            let report = serde_json::json!({ "ok": ok, "diagnostics": self.diagnostics });
+            println!("{}", report);
+        }
+        ok
+    }
+}
+
+/// Checks defined workspaces to ensure no ID collisions occur.
+pub fn check_workspace_ids(workspaces: Option<Vec<Workspace>>, report: &mut Report) {
+    let wss = match workspaces {
+        Some(wss) => wss,
+        None => return,
+    };
+    report.detail("Checking config for valid workspace definitions.");
+    if wss.iter().any(|ws| ws.output.is_some()) && wss.iter().any(|ws| ws.output.is_none()) {
+        report.warn("workspaces", "Your config defines workspaces both by output and by geometry. Workspaces without an output will be ignored.");
+    }
+    let ids = crate::get_workspace_ids(&wss);
+    if ids.iter().any(std::option::Option::is_some) {
+        if !crate::all_ids_some(&ids) {
+            report.error("workspaces", "Your config specifies an ID for some but not all workspaces. This can lead to ID collisions and is not allowed. The default config will be used instead.");
+        } else if !crate::all_ids_unique(&ids) {
+            report.error("workspaces", "Your config contains duplicate workspace IDs. Please assign unique IDs to workspaces. The default config will be used instead.");
+        }
+    }
+}
+
+/// Check all keybinds to ensure that required values are provided
+/// Checks to see if value is provided (if required)
+/// Checks to see if keys and modifiers are valid against Xkeysym
+/// Checks that no two keybinds share a key combination once `modkey` and `mousekey` are resolved
+/// Checks that no keybind uses `mousekey` alone, which is held to drag windows
+/// Checks that scratchpads, layouts and aliases used by the commands are defined
+pub fn check_keybinds(config: &Config, toml_keybinds: Vec<Keybind>, report: &mut Report) {
+    report.section("Checking keybinds");
+    for keybind in toml_keybinds {
+        if let Err(err) = leftwm_core::Keybind::try_from(keybind.clone()) {
+            let combination = key_combination(&keybind.modifier, &keybind.key);
+            report.keybind_error(combination, err);
+        }
+    }
+    for (name, modifier) in [("modkey", &config.modkey), ("mousekey", &config.mousekey)] {
+        if utils::xkeysym_lookup::into_mod(modifier) == 0 {
+            report.error(
+                "keybinds",
+                format!(
+                    "`{}` is set to `{}`, which is not a valid modifier",
+                    name, modifier
+                ),
+            );
+        }
+    }
+    let scratchpads: Vec<&str> = config
+        .scratchpad
+        .iter()
+        .flatten()
+        .map(|s| s.name.as_str())
+        .collect();
+
+    let mut bindings = HashMap::new();
+    for keybind in &config.keybind {
+        report.detail(format!("Keybind: {:?}", keybind));
+        let combination = key_combination(&keybind.modifier, &keybind.key);
+        if utils::xkeysym_lookup::into_keysym(&keybind.key).is_none() {
+            report.keybind_error(
+                combination.clone(),
+                format!("Key `{}` is not valid", keybind.key),
+            );
+        }
+        for m in &keybind.modifier {
+            if m != "modkey" && m != "mousekey" && utils::xkeysym_lookup::into_mod(m) == 0 {
+                report.keybind_error(
+                    combination.clone(),
+                    format!("Modifier `{}` is not valid", m),
+                );
+            }
+        }
+        if keybind.modifier == ["mousekey"] {
+            report.keybind_warning(
+                combination.clone(),
+                "`mousekey` alone is held to move and resize windows with the mouse, \
+                the keybind can get in the way of dragging. Add another modifier",
+            );
+        }
+        for err in undefined_names(config, &scratchpads, &keybind.command) {
+            report.keybind_error(combination.clone(), err);
+        }
+
+        // Compare the modifier masks so `modkey`, `mousekey` and aliases like `Alt` and `Mod1`
+        // are seen as the same key combination.
+        let modifiers: Vec<String> = keybind
+            .modifier
+            .iter()
+            .map(|m| match m.as_str() {
+                "modkey" => config.modkey.clone(),
+                "mousekey" => config.mousekey.clone(),
+                _ => m.clone(),
+            })
+            .collect();
+        let mask = utils::xkeysym_lookup::into_modmask(&modifiers);
+        if let Some((conflict, conflict_command)) = bindings.get(&(mask, keybind.key.clone())) {
+            report.keybind_error(
+                combination,
+                format!(
+                    "Key combination is already bound to {:?} by {}, {:?} cannot be bound to it as well",
+                    conflict_command, conflict, keybind.command
+                ),
+            );
+        } else {
+            bindings.insert(
+                (mask, keybind.key.clone()),
+                (combination, keybind.command.clone()),
+            );
+        }
+    }
+    if report.errors("keybinds") == 0 {
+        report.ok("All keybinds OK");
+    }
+}
+
+/// The scratchpads, layouts and aliases `command` uses that are not defined, also looking in the
+/// commands of sequences.
+fn undefined_names(config: &Config, scratchpads: &[&str], command: &Command) -> Vec<String> {
+    match command {
+        Command::ToggleScratchPad(name) if !scratchpads.contains(&name.as_str()) => {
+            vec![format!(
+                "Scratchpad `{}` is not defined in [[scratchpad]]",
+                name
+            )]
+        }
+        Command::SetLayout(layout) if !config.layouts.contains(layout) => {
+            vec![format!("Layout `{:?}` is not in `layouts`", layout)]
+        }
+        Command::Alias(name) if !config.alias.contains_key(name) => {
+            vec![format!("Alias `{}` is not defined in [alias]", name)]
+        }
+        Command::Sequence(commands) => commands
+            .iter()
+            .flat_map(|command| undefined_names(config, scratchpads, command))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Checks that the commands of each alias are valid, and that aliases don't use themselves.
+pub fn check_aliases(config: &Config, report: &mut Report) {
+    if config.alias.is_empty() {
+        return;
+    }
+    report.section("Checking aliases");
+    let mut aliases = HashMap::new();
+    for (name, commands) in &config.alias {
+        match crate::parse_alias(commands) {
+            Ok(commands) => {
+                aliases.insert(name.clone(), commands);
+            }
+            Err(err) => report.error("aliases", format!("Alias `{}`: {:#}", name, err)),
+        }
+    }
+    for name in aliases.keys() {
+        if let Err(err) = leftwm_core::resolve_alias(&aliases, name) {
+            report.error("aliases", format!("Alias `{}`: {}", name, err));
+        }
+    }
+    if report.errors("aliases") == 0 {
+        report.ok("All aliases OK");
+    }
+}
+
+/// Runs the commands of the keybinds in a simulated `LeftWM` with a few screens and windows,
+/// reporting commands that panic or leave it in an invalid state.
+pub fn check_simulation(config: &Config, report: &mut Report) {
+    report.section("Simulating keybinds");
+    let simulation = simulation::simulate(config);
+    for command in &simulation.skipped {
+        report.detail(format!("Skipped {:?}, it has side effects", command));
+    }
+    for problem in &simulation.problems {
+        report.error("simulation", problem);
+    }
+    if simulation.problems.is_empty() {
+        report.ok(&format!(
+            "Ran {} commands OK, skipped {}",
+            simulation.commands.len(),
+            simulation.skipped.len()
+        ));
+    }
+}
+
+/// Formats a key combination like `modkey + Shift + q`.
+pub fn key_combination(modifier: &[String], key: &str) -> String {
+    modifier
+        .iter()
+        .map(String::as_str)
+        .chain(std::iter::once(key))
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+/// Checks that `XDG_RUNTIME_DIR` is set, as `loginctl` (elogind or systemd) would do. We assume
+/// that if `loginctl` is in the path it's all good.
+pub fn check_environment(
+    xdg_runtime_dir: Result<String, VarError>,
+    has_loginctl: bool,
+    report: &mut Report,
+) {
+    match (xdg_runtime_dir, has_loginctl) {
+        (Ok(val), true) => {
+            report.detail(format!(":: XDG_RUNTIME_DIR: {}, LOGINCTL OKAY", val));
+            report.ok("Environment OK");
+        }
+        (Ok(val), false) => {
+            report.detail(format!(
+                ":: XDG_RUNTIME_DIR: {}, LOGINCTL not installed",
+                val
+            ));
+            report.ok("Environment OK (has XDG_RUNTIME_DIR)");
+        }
+        (Err(e), false) => {
+            report.detail(format!(":: XDG_RUNTIME_DIR_ERROR: {:?}, LOGINCTL BAD", e));
+            report.error(
+                "environment",
+                "Elogind not installed/operating and no alternative XDG_RUNTIME_DIR is set. \
+                See https://github.com/leftwm/leftwm/wiki/XDG_RUNTIME_DIR for more information.",
+            );
+        }
+        (Err(e), true) => {
+            report.detail(format!(":: XDG_RUNTIME_DIR: {:?}, LOGINCTL OKAY", e));
+            report.warn(
+                "environment",
+                "Elogind/systemd installed but XDG_RUNTIME_DIR not set. This may be because elogind isn't started.",
+            );
+        }
+    }
+}
+
+/// The files a theme needs that are not in `filepaths`.
+#[must_use]
+pub fn missing_expected_file<'a>(filepaths: &[PathBuf]) -> Vec<&'a str> {
+    vec!["up", "down", "theme.toml"]
+        .into_iter()
+        .filter(|f| !filepaths.iter().any(|fp| fp.ends_with(f)))
+        .collect()
+}
+
+/// Checks that the contents of `theme.toml` are a valid theme.
+/// # Errors
+///
+/// Errors if the theme cannot be parsed or has invalid margins.
+pub fn check_theme_toml_contents(contents: &str) -> Result<()> {
+    let theme = match toml::from_str::<ThemeSetting>(contents) {
+        Ok(theme) => theme,
+        Err(err) => bail!("Could not parse theme file: {}", err),
+    };
+    if let Err(err) = Margins::try_from(theme.margin) {
+        bail!("Invalid `margin` in theme file: {}", err);
+    }
+    if let Some(Err(err)) = theme.workspace_margin.map(Margins::try_from) {
+        bail!("Invalid `workspace_margin` in theme file: {}", err);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BaseCommand;
+    use leftwm_core::config::ScratchPad;
+    use leftwm_core::layouts::Layout;
+
+    fn new_report() -> Report {
+        Report::new(true, false)
+    }
+
+    /// Whether the report has a diagnostic of `severity` containing `text`.
+    fn has(report: &Report, severity: Severity, text: &str) -> bool {
+        report
+            .diagnostics
+            .iter()
+            .any(|d| d.severity == severity && d.message.contains(text))
+    }
+
+    fn keybind(modifier: &[&str], key: &str, command: Command) -> leftwm_core::Keybind {
+        leftwm_core::Keybind {
+            command,
+            modifier: modifier.iter().map(ToString::to_string).collect(),
+            key: key.to_string(),
+        }
+    }
+
+    fn config(keybinds: Vec<leftwm_core::Keybind>) -> Config {
+        Config {
+            keybind: keybinds,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn the_default_config_should_have_no_problems() {
+        let mut report = new_report();
+        let config = Config::default();
+        check_workspace_ids(config.workspaces.clone(), &mut report);
+        check_keybinds(&config, vec![], &mut report);
+        check_aliases(&config, &mut report);
+        check_simulation(&config, &mut report);
+        assert!(report.diagnostics.is_empty(), "{:?}", report.diagnostics);
+        assert!(report.finish());
+    }
+
+    #[test]
+    fn workspace_ids_should_be_all_set_and_unique() {
+        let workspace = |id, output: Option<&str>| Workspace {
+            id,
+            output: output.map(ToString::to_string),
+            ..Workspace::default()
+        };
+        let mut report = new_report();
+        check_workspace_ids(
+            Some(vec![workspace(Some(0), None), workspace(None, None)]),
+            &mut report,
+        );
+        assert!(has(&report, Severity::Error, "some but not all"));
+
+        let mut report = new_report();
+        check_workspace_ids(
+            Some(vec![workspace(Some(1), None), workspace(Some(1), None)]),
+            &mut report,
+        );
+        assert!(has(&report, Severity::Error, "duplicate workspace IDs"));
+
+        let mut report = new_report();
+        let workspaces = vec![workspace(Some(0), Some("DP-1")), workspace(Some(1), None)];
+        check_workspace_ids(Some(workspaces), &mut report);
+        assert!(has(
+            &report,
+            Severity::Warning,
+            "both by output and by geometry"
+        ));
+    }
+
+    #[test]
+    fn invalid_toml_keybinds_should_be_reported() {
+        let mut report = new_report();
+        let toml_keybind = Keybind {
+            command: BaseCommand::GotoTag,
+            value: Some("one".to_string()),
+            modifier: vec!["modkey".to_string()],
+            key: "1".to_string(),
+        };
+        check_keybinds(&config(vec![]), vec![toml_keybind], &mut report);
+        assert!(has(&report, Severity::Error, "tag index"));
+        assert_eq!(report.diagnostics[0].keybind.as_deref(), Some("modkey + 1"));
+    }
+
+    #[test]
+    fn keys_and_modifiers_should_be_valid() {
+        let mut report = new_report();
+        let mut config = config(vec![
+            keybind(&["modkey"], "NotAKey", Command::CloseWindow),
+            keybind(&["Hyperish"], "q", Command::CloseWindow),
+        ]);
+        config.mousekey = "Mod42".to_string();
+        check_keybinds(&config, vec![], &mut report);
+        assert!(has(&report, Severity::Error, "Key `NotAKey` is not valid"));
+        assert!(has(
+            &report,
+            Severity::Error,
+            "Modifier `Hyperish` is not valid"
+        ));
+        assert!(has(
+            &report,
+            Severity::Error,
+            "`mousekey` is set to `Mod42`"
+        ));
+    }
+
+    #[test]
+    fn commands_should_use_defined_scratchpads_layouts_and_aliases() {
+        let mut report = new_report();
+        let mut config = config(vec![
+            keybind(
+                &["modkey"],
+                "a",
+                Command::ToggleScratchPad("notes".to_string()),
+            ),
+            keybind(&["modkey"], "b", Command::SetLayout(Layout::Monocle)),
+            keybind(&["modkey"], "c", Command::Alias("missing".to_string())),
+            keybind(
+                &["modkey"],
+                "d",
+                Command::Sequence(vec![
+                    Command::CloseWindow,
+                    Command::Alias("unknown".to_string()),
+                ]),
+            ),
+        ]);
+        config.layouts = vec![Layout::MainAndVertStack];
+        config.scratchpad = Some(vec![ScratchPad {
+            name: "music".to_string(),
+            value: "ncmpcpp".to_string(),
+            x: None,
+            y: None,
+            height: None,
+            width: None,
+        }]);
+        check_keybinds(&config, vec![], &mut report);
+        assert!(has(
+            &report,
+            Severity::Error,
+            "Scratchpad `notes` is not defined"
+        ));
+        assert!(has(
+            &report,
+            Severity::Error,
+            "Layout `Monocle` is not in `layouts`"
+        ));
+        assert!(has(
+            &report,
+            Severity::Error,
+            "Alias `missing` is not defined"
+        ));
+        assert!(has(
+            &report,
+            Severity::Error,
+            "Alias `unknown` is not defined"
+        ));
+    }
+
+    #[test]
+    fn key_combinations_should_be_bound_once() {
+        let mut report = new_report();
+        let mut config = config(vec![
+            keybind(&["modkey"], "q", Command::CloseWindow),
+            keybind(&["Mod4"], "q", Command::ToggleFloating),
+        ]);
+        config.modkey = "Mod4".to_string();
+        check_keybinds(&config, vec![], &mut report);
+        assert!(has(
+            &report,
+            Severity::Error,
+            "already bound to CloseWindow"
+        ));
+    }
+
+    #[test]
+    fn keybinds_should_not_use_the_mousekey_alone() {
+        let mut report = new_report();
+        let config = config(vec![
+            keybind(&["mousekey"], "w", Command::CloseWindow),
+            keybind(&["mousekey", "Shift"], "w", Command::ToggleFloating),
+        ]);
+        check_keybinds(&config, vec![], &mut report);
+        assert_eq!(report.diagnostics.len(), 1);
+        assert!(has(
+            &report,
+            Severity::Warning,
+            "can get in the way of dragging"
+        ));
+        assert_eq!(
+            report.diagnostics[0].keybind.as_deref(),
+            Some("mousekey + w")
+        );
+    }
+
+    #[test]
+    fn aliases_should_be_valid_and_not_use_themselves() {
+        let mut report = new_report();
+        let mut config = config(vec![]);
+        config
+            .alias
+            .insert("broken".to_string(), vec!["GotoTag x".to_string()]);
+        config
+            .alias
+            .insert("loop".to_string(), vec!["Alias loop".to_string()]);
+        check_aliases(&config, &mut report);
+        assert!(has(&report, Severity::Error, "Alias `broken`"));
+        assert!(has(&report, Severity::Error, "Alias `loop` uses itself"));
+    }
+
+    #[test]
+    fn the_environment_should_have_a_runtime_dir() {
+        let mut report = new_report();
+        check_environment(Ok("/run/user/1000".to_string()), false, &mut report);
+        assert!(report.diagnostics.is_empty());
+        check_environment(Err(VarError::NotPresent), true, &mut report);
+        assert!(has(&report, Severity::Warning, "XDG_RUNTIME_DIR not set"));
+        check_environment(Err(VarError::NotPresent), false, &mut report);
+        assert!(has(&report, Severity::Error, "Elogind not installed"));
+    }
+
+    #[test]
+    fn themes_should_have_their_files_and_valid_margins() {
+        let files = vec![PathBuf::from("/themes/current/up")];
+        assert_eq!(missing_expected_file(&files), vec!["down", "theme.toml"]);
+        let theme = |margin: &str| {
+            "border_width = 1\nmargin = ".to_string()
+                + margin
+                + "\ndefault_border_color = '#222222'\nfloating_border_color = '#005500'\n\
+                focused_border_color = '#FFB53A'"
+        };
+        assert!(check_theme_toml_contents(&theme("5")).is_ok());
+        let error = check_theme_toml_contents(&theme("[1, 2, 3, 4, 5]")).unwrap_err();
+        assert!(error.to_string().contains("Invalid `margin`"));
+        let error = check_theme_toml_contents("margin = 5").unwrap_err();
+        assert!(error.to_string().contains("Could not parse"));
+    }
+}
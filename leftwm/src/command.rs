@@ -1,11 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-/*  TODO this code is temporary. Due to the limitations of TOML we cannot serialize leftwm_core::Command
-*      easily. If we replace TOML by JSON/JSON5/YAML we will be able to remove this code and a
-*      bunch of validation in leftwm-check.rs. This requires to deprecate the TOML config file,
-*      thus making a breaking change.
-*
-*      https://play.rust-lang.org/?version=stable&mode=debug&edition=2018&gist=59232ae3a6f902fc3a3a7a09d1d48c80
+/*  The command names of `config.toml` keybinds. Due to the limitations of TOML we cannot
+*      serialize leftwm_core::Command easily, so its arguments are given in a separate `value`.
+*      The RON, JSON and YAML configs use leftwm_core::Command directly, see `ConfigFormat`.
+*      Once TOML is deprecated this code and the matching checks in leftwm-check can be removed.
 */
 
 // Because this is temporary, we will allow this clippy lint to be bypassed
@@ -14,12 +12,15 @@ use serde::{Deserialize, Serialize};
 pub enum BaseCommand {
     Execute,
     CloseWindow,
-    SwapTags,
+    SwapScreens,
     SoftReload,
     HardReload,
     ToggleScratchPad,
     ToggleFullScreen,
     ToggleSticky,
+    ToggleAbove,
+    ToggleBelow,
+    ToggleMaximize,
     GotoTag,
     FloatingToTile,
     TileToFloating,
@@ -33,8 +34,8 @@ pub enum BaseCommand {
     FocusWindowDown,
     FocusWorkspaceNext,
     FocusWorkspacePrevious,
-    MoveToTag,
-    MoveToLastWorkspace,
+    SendWindowToTag,
+    MoveWindowToLastWorkspace,
     MoveWindowToNextWorkspace,
     MoveWindowToPreviousWorkspace,
     MouseMoveWindow,
@@ -45,7 +46,80 @@ pub enum BaseCommand {
     IncreaseMainWidth,
     DecreaseMainWidth,
     SetMarginMultiplier,
+    MoveFloating,
+    ResizeFloating,
+    CenterFloating,
+    SnapFloating,
+    ToggleStruts,
+    ToggleGutters,
+    ToggleDocks,
+    ZenMode,
+    SplitWorkspace,
+    MergeWorkspace,
+    ResizeWorkspaceSplit,
+    Sequence,
+    Alias,
     // Custom commands
     UnloadTheme,
     LoadTheme,
 }
+
+impl BaseCommand {
+    /// The name of the command, as parsed by `leftwm_core::Command`.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Execute => "Execute",
+            Self::CloseWindow => "CloseWindow",
+            Self::SwapScreens => "SwapScreens",
+            Self::SoftReload => "SoftReload",
+            Self::HardReload => "HardReload",
+            Self::ToggleScratchPad => "ToggleScratchPad",
+            Self::ToggleFullScreen => "ToggleFullScreen",
+            Self::ToggleSticky => "ToggleSticky",
+            Self::ToggleAbove => "ToggleAbove",
+            Self::ToggleBelow => "ToggleBelow",
+            Self::ToggleMaximize => "ToggleMaximize",
+            Self::GotoTag => "GotoTag",
+            Self::FloatingToTile => "FloatingToTile",
+            Self::TileToFloating => "TileToFloating",
+            Self::ToggleFloating => "ToggleFloating",
+            Self::MoveWindowUp => "MoveWindowUp",
+            Self::MoveWindowDown => "MoveWindowDown",
+            Self::MoveWindowTop => "MoveWindowTop",
+            Self::FocusNextTag => "FocusNextTag",
+            Self::FocusPreviousTag => "FocusPreviousTag",
+            Self::FocusWindowUp => "FocusWindowUp",
+            Self::FocusWindowDown => "FocusWindowDown",
+            Self::FocusWorkspaceNext => "FocusWorkspaceNext",
+            Self::FocusWorkspacePrevious => "FocusWorkspacePrevious",
+            Self::SendWindowToTag => "SendWindowToTag",
+            Self::MoveWindowToLastWorkspace => "MoveWindowToLastWorkspace",
+            Self::MoveWindowToNextWorkspace => "MoveWindowToNextWorkspace",
+            Self::MoveWindowToPreviousWorkspace => "MoveWindowToPreviousWorkspace",
+            Self::MouseMoveWindow => "MouseMoveWindow",
+            Self::NextLayout => "NextLayout",
+            Self::PreviousLayout => "PreviousLayout",
+            Self::SetLayout => "SetLayout",
+            Self::RotateTag => "RotateTag",
+            Self::IncreaseMainWidth => "IncreaseMainWidth",
+            Self::DecreaseMainWidth => "DecreaseMainWidth",
+            Self::SetMarginMultiplier => "SetMarginMultiplier",
+            Self::MoveFloating => "MoveFloating",
+            Self::ResizeFloating => "ResizeFloating",
+            Self::CenterFloating => "CenterFloating",
+            Self::SnapFloating => "SnapFloating",
+            Self::ToggleStruts => "ToggleStruts",
+            Self::ToggleGutters => "ToggleGutters",
+            Self::ToggleDocks => "ToggleDocks",
+            Self::ZenMode => "ZenMode",
+            Self::SplitWorkspace => "SplitWorkspace",
+            Self::MergeWorkspace => "MergeWorkspace",
+            Self::ResizeWorkspaceSplit => "ResizeWorkspaceSplit",
+            Self::Sequence => "Sequence",
+            Self::Alias => "Alias",
+            Self::UnloadTheme => "UnloadTheme",
+            Self::LoadTheme => "LoadTheme",
+        }
+    }
+}
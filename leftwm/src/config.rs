@@ -1,15 +1,22 @@
 //! `LeftWM` general configuration
 
-use super::{BaseCommand, ThemeSetting};
-use anyhow::{Context, Result};
+use super::{
+    migration::{self, CONFIG_VERSION},
+    BaseCommand, ThemeSetting,
+};
+use anyhow::{bail, Context, Result};
 use leftwm_core::{
-    config::{ScratchPad, Workspace},
+    config::{Hooks, ScratchPad, Workspace},
     layouts::{Layout, LAYOUTS},
-    models::{FocusBehaviour, Gutter, LayoutMode, Margins, Size},
+    models::{
+        FloatingPlacement, FocusBehaviour, FocusStealingPolicy, Gutter, LayoutMode, Margins, Size,
+        TagMode,
+    },
     state::State,
-    Manager,
+    CommandError, DisplayServer, Manager,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::convert::TryInto;
 use std::default::Default;
@@ -18,7 +25,6 @@ use std::fs;
 use std::fs::File;
 use std::io::prelude::*;
 use std::path::{Path, PathBuf};
-use std::str::FromStr;
 use xdg::BaseDirectories;
 
 /// Path to file where state will be dumper upon soft reload.
@@ -36,75 +42,8 @@ impl TryFrom<Keybind> for leftwm_core::Keybind {
     type Error = anyhow::Error;
 
     fn try_from(k: Keybind) -> Result<Self> {
-        let command = match k.command {
-            BaseCommand::Execute => {
-                leftwm_core::Command::Execute(k.value.context("missing command for Execute")?)
-            }
-            BaseCommand::CloseWindow => leftwm_core::Command::CloseWindow,
-            BaseCommand::SwapTags => leftwm_core::Command::SwapScreens,
-            BaseCommand::SoftReload => leftwm_core::Command::SoftReload,
-            BaseCommand::HardReload => leftwm_core::Command::HardReload,
-            BaseCommand::ToggleScratchPad => leftwm_core::Command::ToggleScratchPad(
-                k.value.context("missing name for ToggleScratchPad")?,
-            ),
-            BaseCommand::ToggleFullScreen => leftwm_core::Command::ToggleFullScreen,
-            BaseCommand::ToggleSticky => leftwm_core::Command::ToggleSticky,
-            BaseCommand::GotoTag => leftwm_core::Command::GotoTag(
-                usize::from_str(&k.value.context("missing index value for GotoTag")?)
-                    .context("invalid index value for GotoTag")?,
-            ),
-            BaseCommand::FloatingToTile => leftwm_core::Command::FloatingToTile,
-            BaseCommand::TileToFloating => leftwm_core::Command::TileToFloating,
-            BaseCommand::ToggleFloating => leftwm_core::Command::ToggleFloating,
-            BaseCommand::MoveWindowUp => leftwm_core::Command::MoveWindowUp,
-            BaseCommand::MoveWindowDown => leftwm_core::Command::MoveWindowDown,
-            BaseCommand::MoveWindowTop => leftwm_core::Command::MoveWindowTop,
-            BaseCommand::FocusNextTag => leftwm_core::Command::FocusNextTag,
-            BaseCommand::FocusPreviousTag => leftwm_core::Command::FocusPreviousTag,
-            BaseCommand::FocusWindowUp => leftwm_core::Command::FocusWindowUp,
-            BaseCommand::FocusWindowDown => leftwm_core::Command::FocusWindowDown,
-            BaseCommand::FocusWorkspaceNext => leftwm_core::Command::FocusWorkspaceNext,
-            BaseCommand::FocusWorkspacePrevious => leftwm_core::Command::FocusWorkspacePrevious,
-            BaseCommand::MoveToTag => leftwm_core::Command::SendWindowToTag(
-                usize::from_str(&k.value.context("missing index value for SendWindowToTag")?)
-                    .context("invalid index value for SendWindowToTag")?,
-            ),
-            BaseCommand::MoveToLastWorkspace => leftwm_core::Command::MoveWindowToLastWorkspace,
-            BaseCommand::MoveWindowToNextWorkspace => {
-                leftwm_core::Command::MoveWindowToNextWorkspace
-            }
-            BaseCommand::MoveWindowToPreviousWorkspace => {
-                leftwm_core::Command::MoveWindowToPreviousWorkspace
-            }
-            BaseCommand::MouseMoveWindow => leftwm_core::Command::MouseMoveWindow,
-            BaseCommand::NextLayout => leftwm_core::Command::NextLayout,
-            BaseCommand::PreviousLayout => leftwm_core::Command::PreviousLayout,
-            BaseCommand::SetLayout => leftwm_core::Command::SetLayout(
-                Layout::from_str(&k.value.context("missing layout for SetLayout")?)
-                    .context("could not parse layout for command SetLayout")?,
-            ),
-            BaseCommand::RotateTag => leftwm_core::Command::RotateTag,
-            BaseCommand::IncreaseMainWidth => leftwm_core::Command::IncreaseMainWidth(
-                i8::from_str(&k.value.context("missing value for IncreaseMainWidth")?)
-                    .context("invalid value for IncreaseMainWidth")?,
-            ),
-            BaseCommand::DecreaseMainWidth => leftwm_core::Command::DecreaseMainWidth(
-                i8::from_str(&k.value.context("missing value for DecreaseMainWidth")?)
-                    .context("invalid value for DecreaseMainWidth")?,
-            ),
-            BaseCommand::SetMarginMultiplier => leftwm_core::Command::SetMarginMultiplier(
-                f32::from_str(
-                    &k.value
-                        .context("missing index value for SetMarginMultiplier")?,
-                )
-                .context("invalid index value for SetMarginMultiplier")?,
-            ),
-            BaseCommand::UnloadTheme => leftwm_core::Command::Other("UnloadTheme".into()),
-            BaseCommand::LoadTheme => leftwm_core::Command::Other(format!(
-                "LoadTheme {}",
-                k.value.context("missing index value for LoadTheme")?,
-            )),
-        };
+        let value = k.value.as_deref().unwrap_or_default();
+        let command = leftwm_core::Command::from_parts(k.command.name(), value)?;
 
         Ok(Self {
             command,
@@ -115,26 +54,267 @@ impl TryFrom<Keybind> for leftwm_core::Keybind {
 }
 
 /// General configuration
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(default)]
-pub struct Config {
+///
+/// `K` is the type of the keybinds. At runtime these are `leftwm_core::Keybind`, holding typed
+/// commands. `config.toml` is read as `Config<Keybind>` instead, because TOML cannot represent
+/// commands with arguments.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(
+    default,
+    bound(deserialize = "K: Deserialize<'de>, Config<K>: Default")
+)]
+pub struct Config<K = leftwm_core::Keybind> {
+    /// The version of the config schema, see [`crate::migration`].
+    pub version: i64,
     pub modkey: String,
     pub mousekey: String,
     pub workspaces: Option<Vec<Workspace>>,
     pub tags: Option<Vec<String>>,
     pub max_window_width: Option<Size>,
+    pub honour_size_hints: bool,
     pub layouts: Vec<Layout>,
     pub layout_mode: LayoutMode,
+    //"shared" tags, or each monitor with its own tags with "per_monitor"
+    pub tag_mode: TagMode,
     pub scratchpad: Option<Vec<ScratchPad>>,
     //of you are on tag "1" and you goto tag "1" this takes you to the previous tag
     pub disable_current_tag_swap: bool,
     pub focus_behaviour: FocusBehaviour,
     pub focus_new_windows: bool,
-    pub keybind: Vec<Keybind>,
+    pub floating_placement: Option<FloatingPlacement>,
+    pub focus_stealing_policy: FocusStealingPolicy,
+    //per class overrides of the focus stealing policy, like `Firefox = "urgent"`
+    pub focus_stealing_overrides: HashMap<String, FocusStealingPolicy>,
+    pub keybind: Vec<K>,
     pub state: Option<PathBuf>,
+    /// Commands run on events, the ones of the theme take precedence.
+    pub hooks: Hooks,
+    /// Named lists of commands, like `follow3 = ["SendWindowToTag 3", "GotoTag 3"]`.
+    pub alias: HashMap<String, Vec<String>>,
+    /// The commands of `alias`, parsed when the config is read. Invalid aliases are left out.
+    #[serde(skip)]
+    pub alias_commands: HashMap<String, Vec<leftwm_core::Command>>,
 
     #[serde(skip)]
     pub theme_setting: ThemeSetting,
+    /// The theme file loaded with `LoadTheme`, reloaded along with the config.
+    #[serde(skip)]
+    pub theme_path: Option<PathBuf>,
+}
+
+/// The file formats the config can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Ron,
+    Json,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Formats in the order their config files are looked up.
+    pub const ALL: [Self; 4] = [Self::Ron, Self::Json, Self::Yaml, Self::Toml];
+
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "toml" => Some(Self::Toml),
+            "ron" => Some(Self::Ron),
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Toml => "toml",
+            Self::Ron => "ron",
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+        }
+    }
+
+    /// Reads the config file at `path`, including the files listed in `include` for TOML.
+    ///
+    /// # Errors
+    ///
+    /// Will error if a file cannot be read or is not a valid config in this format.
+    pub fn read(self, path: &Path) -> Result<Config> {
+        match self {
+            Self::Toml => Ok(Config::from(read_toml(path)?).with_alias_commands()),
+            _ => self.parse(&fs::read_to_string(path)?),
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Will error if the contents are not a valid config in this format.
+    pub fn parse(self, contents: &str) -> Result<Config> {
+        let config: Config = match self {
+            Self::Toml => toml::from_str::<Config<Keybind>>(contents)?.into(),
+            Self::Ron => ron::from_str(contents)?,
+            Self::Json => serde_json::from_str(contents)?,
+            Self::Yaml => serde_yaml::from_str(contents)?,
+        };
+        Ok(config.with_alias_commands())
+    }
+
+    /// # Errors
+    ///
+    /// Will error if the config cannot be serialized, which is always the case for TOML as it
+    /// cannot represent commands with arguments.
+    pub fn serialize(self, config: &Config) -> Result<String> {
+        Ok(match self {
+            Self::Toml => bail!("typed keybinds cannot be written as TOML"),
+            Self::Ron => ron::ser::to_string_pretty(config, ron::ser::PrettyConfig::default())?,
+            Self::Json => serde_json::to_string_pretty(config)?,
+            Self::Yaml => serde_yaml::to_string(config)?,
+        })
+    }
+}
+
+/// Returns the path of the config file, trying `config.ron`, `config.json`, `config.yaml` and
+/// `config.yml` before `config.toml`.
+///
+/// # Errors
+///
+/// Will error if `BaseDirectories` doesn't exist or the config directory cannot be created.
+pub fn config_file() -> Result<PathBuf> {
+    let path = BaseDirectories::with_prefix("leftwm")?;
+    let found = ConfigFormat::ALL
+        .iter()
+        .map(|format| format!("config.{}", format.extension()))
+        .chain(std::iter::once("config.yml".to_owned()))
+        .find_map(|filename| path.find_config_file(filename));
+    match found {
+        Some(config_filename) => Ok(config_filename),
+        None => Ok(path.place_config_file("config.toml")?),
+    }
+}
+
+/// Reads a `config.toml` along with the files listed in its `include`.
+///
+/// Included files are merged over the including one in order, so later files win: lists are
+/// appended, tables are merged key by key and other values are overridden. Paths may use `~` and
+/// environment variables, like `hosts/${HOSTNAME}.toml`, and are relative to the including file.
+///
+/// # Errors
+///
+/// Will error if a file cannot be read or parsed, if a path cannot be expanded or if the includes
+/// are nested too deeply, most likely because a file includes itself.
+pub fn read_toml(path: &Path) -> Result<Config<Keybind>> {
+    Ok(read_toml_value(path, 0)?.try_into()?)
+}
+
+/// Returns the files included by a `config.toml`, directly or through other included files.
+///
+/// # Errors
+///
+/// Will error if a file cannot be read or parsed, or a path cannot be expanded.
+pub fn toml_includes(path: &Path) -> Result<Vec<PathBuf>> {
+    let mut includes = vec![];
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(path) = pending.pop() {
+        if includes.len() > MAX_INCLUDES {
+            bail!("too many nested includes in {}", path.display());
+        }
+        let value: toml::Value = toml::from_str(&fs::read_to_string(&path)?)?;
+        let paths = include_paths(&path, value.get("include"))?;
+        includes.extend(paths.iter().cloned());
+        pending.extend(paths);
+    }
+    Ok(includes)
+}
+
+const MAX_INCLUDES: usize = 32;
+
+fn read_toml_value(path: &Path, depth: usize) -> Result<toml::Value> {
+    if depth > MAX_INCLUDES {
+        bail!("too many nested includes in {}", path.display());
+    }
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("cannot read config file {}", path.display()))?;
+    let contents = match migration::migrate(&contents) {
+        Ok(migrated) => {
+            if !migrated.changes.is_empty() {
+                log::warn!(
+                    "{} uses deprecated settings, run `leftwm-check --migrate` to update it: {}",
+                    path.display(),
+                    migrated.changes.join(", ")
+                );
+            }
+            migrated.contents
+        }
+        Err(err) => {
+            log::warn!("Cannot migrate {}: {}", path.display(), err);
+            contents
+        }
+    };
+    let mut value: toml::Value = toml::from_str(&contents)
+        .with_context(|| format!("cannot parse config file {}", path.display()))?;
+    let include = value
+        .as_table_mut()
+        .and_then(|table| table.remove("include"));
+    for included in include_paths(path, include.as_ref())? {
+        merge_toml(&mut value, read_toml_value(&included, depth + 1)?);
+    }
+    Ok(value)
+}
+
+fn include_paths(path: &Path, include: Option<&toml::Value>) -> Result<Vec<PathBuf>> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let include = match include {
+        None => return Ok(vec![]),
+        Some(toml::Value::Array(include)) => include,
+        Some(_) => bail!("`include` in {} must be a list of paths", path.display()),
+    };
+    include
+        .iter()
+        .map(|included| {
+            let included = included.as_str().with_context(|| {
+                format!("`include` in {} must be a list of paths", path.display())
+            })?;
+            let expanded = shellexpand::full_with_context(included, dirs_next::home_dir, env_var)
+                .with_context(|| format!("cannot expand include {}", included))?;
+            Ok(dir.join(expanded.as_ref()))
+        })
+        .collect()
+}
+
+/// Looks up environment variables for includes, falling back to the system hostname for
+/// `HOSTNAME` as shells usually don't export it.
+fn env_var(name: &str) -> Result<Option<String>, env::VarError> {
+    match env::var(name) {
+        Err(env::VarError::NotPresent) if name == "HOSTNAME" => {
+            let mut buf = [0_u8; 64];
+            let hostname = nix::unistd::gethostname(&mut buf)
+                .map_err(|_| env::VarError::NotPresent)?
+                .to_string_lossy()
+                .into_owned();
+            Ok(Some(hostname))
+        }
+        Err(err) => Err(err),
+        Ok(value) => Ok(Some(value)),
+    }
+}
+
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(base_value) => merge_toml(base_value, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (toml::Value::Array(base), toml::Value::Array(overlay)) => base.extend(overlay),
+        (base, overlay) => *base = overlay,
+    }
 }
 
 #[must_use]
@@ -152,33 +332,35 @@ pub fn load() -> Config {
 /// # Errors
 ///
 /// Function will throw an error if `BaseDirectories` doesn't exist, if user doesn't have
-/// permissions to place config.toml, if config.toml cannot be read (access writes, malformed file,
-/// etc.).
+/// permissions to place config.toml, if the config file cannot be read (access writes, malformed
+/// file, etc.).
 /// Function can also error from inability to save config.toml (if it is the first time running
 /// `LeftWM`).
 fn load_from_file() -> Result<Config> {
-    let path = BaseDirectories::with_prefix("leftwm")?;
-    let config_filename = path.place_config_file("config.toml")?;
+    let config_filename = config_file()?;
     if Path::new(&config_filename).exists() {
-        let contents = fs::read_to_string(config_filename)?;
-        let config = toml::from_str(&contents)?;
+        let format = ConfigFormat::from_path(&config_filename).unwrap_or(ConfigFormat::Toml);
+        let config = format.read(&config_filename)?;
         if check_workspace_ids(&config) {
             Ok(config)
         } else {
-            log::warn!("Invalid workspace ID configuration in config.toml. Falling back to default config.");
+            log::warn!(
+                "Invalid workspace ID configuration in {}. Falling back to default config.",
+                config_filename.display()
+            );
             Ok(Config::default())
         }
     } else {
-        let config = Config::default();
+        let config = Config::<Keybind>::default();
         let toml = toml::to_string(&config).unwrap();
         let mut file = File::create(&config_filename)?;
         file.write_all(toml.as_bytes())?;
-        Ok(config)
+        Ok(config.into())
     }
 }
 
 #[must_use]
-pub fn check_workspace_ids(config: &Config) -> bool {
+pub fn check_workspace_ids<K>(config: &Config<K>) -> bool {
     config.workspaces.clone().map_or(true, |wss| {
         let ids = get_workspace_ids(&wss);
         if ids.iter().any(Option::is_some) {
@@ -282,13 +464,6 @@ impl leftwm_core::Config for Config {
                 }
                 keybind
             })
-            .filter_map(|keybind| match TryFrom::try_from(keybind.clone()) {
-                Ok(internal_keybind) => Some(internal_keybind),
-                Err(err) => {
-                    log::error!("Invalid key binding: {}\n{:?}", err, keybind);
-                    None
-                }
-            })
             .collect()
     }
 
@@ -332,32 +507,62 @@ impl leftwm_core::Config for Config {
         self.layout_mode
     }
 
+    fn tag_mode(&self) -> TagMode {
+        self.tag_mode
+    }
+
     fn focus_new_windows(&self) -> bool {
         self.focus_new_windows
     }
 
-    fn command_handler<SERVER>(command: &str, manager: &mut Manager<Self, SERVER>) -> bool {
-        if let Some((command, value)) = command.split_once(' ') {
-            match command {
-                "LoadTheme" => {
-                    if let Some(absolute) = absolute_path(value.trim()) {
-                        manager.config.theme_setting.load(absolute);
-                    } else {
-                        log::warn!("Path submitted does not exist.");
-                    }
-                    return manager.reload_config();
-                }
-                "UnloadTheme" => {
-                    manager.config.theme_setting = ThemeSetting::default();
-                    return manager.reload_config();
-                }
-                _ => {
-                    log::warn!("Command not recognized: {}", command);
-                    return false;
-                }
+    fn floating_placement(&self) -> Option<FloatingPlacement> {
+        self.floating_placement
+    }
+
+    fn focus_stealing_policy(&self) -> FocusStealingPolicy {
+        self.focus_stealing_policy
+    }
+
+    fn focus_stealing_overrides(&self) -> HashMap<String, FocusStealingPolicy> {
+        self.focus_stealing_overrides.clone()
+    }
+
+    fn command_handler<SERVER>(command: &str, manager: &mut Manager<Self, SERVER>) -> bool
+    where
+        SERVER: DisplayServer,
+    {
+        Self::checked_command_handler(command, manager).unwrap_or_else(|err| {
+            log::warn!("{}", err);
+            false
+        })
+    }
+
+    fn checked_command_handler<SERVER>(
+        command: &str,
+        manager: &mut Manager<Self, SERVER>,
+    ) -> Result<bool, CommandError> {
+        let (command, value) = command.split_once(' ').unwrap_or((command, ""));
+        match command {
+            "LoadTheme" => {
+                let path = value.trim();
+                let absolute = absolute_path(path).ok_or_else(|| {
+                    CommandError::CannotLoadTheme(format!("{}: it does not exist", path))
+                })?;
+                manager
+                    .config
+                    .theme_setting
+                    .load(&absolute)
+                    .map_err(|err| CommandError::CannotLoadTheme(format!("{}: {}", path, err)))?;
+                manager.config.theme_path = Some(absolute);
+                Ok(manager.reload_config())
+            }
+            "UnloadTheme" => {
+                manager.config.theme_setting = ThemeSetting::default();
+                manager.config.theme_path = None;
+                Ok(manager.reload_config())
             }
+            _ => Err(CommandError::Unknown(command.to_string())),
         }
-        false
     }
 
     fn border_width(&self) -> i32 {
@@ -415,8 +620,16 @@ impl leftwm_core::Config for Config {
         self.theme_setting.focused_border_color.clone()
     }
 
-    fn on_new_window_cmd(&self) -> Option<String> {
-        self.theme_setting.on_new_window_cmd.clone()
+    fn hooks(&self) -> Hooks {
+        let mut theme_hooks = self.theme_setting.hooks.clone();
+        if theme_hooks.on_new_window.is_none() {
+            theme_hooks.on_new_window = self.theme_setting.on_new_window_cmd.clone();
+        }
+        self.hooks.clone().merge(theme_hooks)
+    }
+
+    fn aliases(&self) -> &HashMap<String, Vec<leftwm_core::Command>> {
+        &self.alias_commands
     }
 
     fn get_list_of_gutters(&self) -> Vec<Gutter> {
@@ -427,6 +640,10 @@ impl leftwm_core::Config for Config {
         self.max_window_width
     }
 
+    fn honour_size_hints(&self) -> bool {
+        self.honour_size_hints
+    }
+
     fn save_state(&self, state: &State) {
         let path = self.state_file();
         let state_file = match File::create(&path) {
@@ -457,9 +674,84 @@ impl leftwm_core::Config for Config {
             Err(err) => log::error!("Cannot open old state: {}", err),
         }
     }
+
+    fn watched_files(&self) -> Vec<PathBuf> {
+        let mut files = vec![];
+        if let Ok(config_filename) = config_file() {
+            if ConfigFormat::from_path(&config_filename) == Some(ConfigFormat::Toml) {
+                files.extend(toml_includes(&config_filename).unwrap_or_default());
+            }
+            files.push(config_filename);
+        }
+        files.extend(self.theme_path.clone());
+        files
+    }
+
+    fn reload_from_files(&mut self) -> bool {
+        let config = config_file().and_then(|config_filename| {
+            let format = ConfigFormat::from_path(&config_filename).unwrap_or(ConfigFormat::Toml);
+            format.read(&config_filename)
+        });
+        match config {
+            Ok(config) if check_workspace_ids(&config) => {
+                *self = Self {
+                    theme_setting: self.theme_setting.clone(),
+                    theme_path: self.theme_path.take(),
+                    ..config
+                };
+                if let Some(theme_path) = &self.theme_path {
+                    if let Err(err) = self.theme_setting.load(theme_path) {
+                        let path = theme_path.display();
+                        log::error!("Could not load theme at path {}: {}", path, err);
+                    }
+                }
+                true
+            }
+            Ok(_) => {
+                log::error!("Invalid workspace ID configuration, keeping the current config.");
+                false
+            }
+            Err(err) => {
+                log::error!("Cannot reload config, keeping the current one: {:?}", err);
+                false
+            }
+        }
+    }
+}
+
+/// Parses the commands of an alias.
+///
+/// # Errors
+///
+/// Will error if one of the commands is invalid.
+pub fn parse_alias(commands: &[String]) -> Result<Vec<leftwm_core::Command>> {
+    commands
+        .iter()
+        .map(|command| {
+            command
+                .parse()
+                .with_context(|| format!("Invalid command `{}`", command))
+        })
+        .collect()
 }
 
 impl Config {
+    /// Parses the commands of the aliases, logging the invalid ones.
+    fn with_alias_commands(mut self) -> Self {
+        self.alias_commands = self
+            .alias
+            .iter()
+            .filter_map(|(name, commands)| match parse_alias(commands) {
+                Ok(commands) => Some((name.clone(), commands)),
+                Err(err) => {
+                    log::error!("Invalid alias {}: {}", name, err);
+                    None
+                }
+            })
+            .collect();
+        self
+    }
+
     fn state_file(&self) -> &Path {
         self.state
             .as_deref()
@@ -468,6 +760,55 @@ impl Config {
 }
 
 impl Default for Config {
+    fn default() -> Self {
+        Config::<Keybind>::default().into()
+    }
+}
+
+/// Converts the keybinds of `config.toml` into typed commands, dropping the invalid ones.
+impl From<Config<Keybind>> for Config {
+    fn from(config: Config<Keybind>) -> Self {
+        let keybind = config
+            .keybind
+            .into_iter()
+            .filter_map(|keybind| match TryFrom::try_from(keybind.clone()) {
+                Ok(internal_keybind) => Some(internal_keybind),
+                Err(err) => {
+                    log::error!("Invalid key binding: {}\n{:?}", err, keybind);
+                    None
+                }
+            })
+            .collect();
+        Self {
+            version: config.version,
+            modkey: config.modkey,
+            mousekey: config.mousekey,
+            workspaces: config.workspaces,
+            tags: config.tags,
+            max_window_width: config.max_window_width,
+            honour_size_hints: config.honour_size_hints,
+            layouts: config.layouts,
+            layout_mode: config.layout_mode,
+            tag_mode: config.tag_mode,
+            scratchpad: config.scratchpad,
+            disable_current_tag_swap: config.disable_current_tag_swap,
+            focus_behaviour: config.focus_behaviour,
+            focus_new_windows: config.focus_new_windows,
+            floating_placement: config.floating_placement,
+            focus_stealing_policy: config.focus_stealing_policy,
+            focus_stealing_overrides: config.focus_stealing_overrides,
+            keybind,
+            state: config.state,
+            hooks: config.hooks,
+            alias: config.alias,
+            alias_commands: config.alias_commands,
+            theme_setting: config.theme_setting,
+            theme_path: config.theme_path,
+        }
+    }
+}
+
+impl Default for Config<Keybind> {
     // We allow this because this function would be difficult to reduce. If someone would like to
     // move the commands builder out, perhaps make a macro, this function could be reduced in size
     // considerably.
@@ -519,14 +860,14 @@ impl Default for Config {
             },
             // Mod + Shift + w => swap the tags on the last to active workspaces
             Keybind {
-                command: BaseCommand::MoveToLastWorkspace,
+                command: BaseCommand::MoveWindowToLastWorkspace,
                 value: None,
                 modifier: vec!["modkey".to_owned(), "Shift".to_owned()],
                 key: "w".to_owned(),
             },
             // Mod + w => move the active window to the previous workspace
             Keybind {
-                command: BaseCommand::SwapTags,
+                command: BaseCommand::SwapScreens,
                 value: None,
                 modifier: vec!["modkey".to_owned()],
                 key: "w".to_owned(),
@@ -648,7 +989,7 @@ impl Default for Config {
         // and "move to workspace"
         for i in 1..WORKSPACES_NUM {
             commands.push(Keybind {
-                command: BaseCommand::MoveToTag,
+                command: BaseCommand::SendWindowToTag,
                 value: Some(i.to_string()),
                 modifier: vec!["modkey".to_owned(), "Shift".to_owned()],
                 key: i.to_string(),
@@ -661,22 +1002,183 @@ impl Default for Config {
             .collect();
 
         Self {
+            version: CONFIG_VERSION,
             workspaces: Some(vec![]),
             tags: Some(tags),
             layouts: LAYOUTS.to_vec(),
             layout_mode: LayoutMode::Workspace,
+            tag_mode: TagMode::Shared,
             // TODO: add sane default for scratchpad config.
             // Currently default values are set in sane_dimension fn.
             scratchpad: Some(vec![]),
             disable_current_tag_swap: false,
             focus_behaviour: FocusBehaviour::Sloppy, // default behaviour: mouse move auto-focuses window
             focus_new_windows: true, // default behaviour: focuses windows on creation
-            modkey: "Mod4".to_owned(), //win key
+            floating_placement: None, // default behaviour: keep the position requested by the window
+            focus_stealing_policy: FocusStealingPolicy::Focus,
+            focus_stealing_overrides: HashMap::new(),
+            modkey: "Mod4".to_owned(),   //win key
             mousekey: "Mod4".to_owned(), //win key
             keybind: commands,
             theme_setting: ThemeSetting::default(),
+            theme_path: None,
             max_window_width: None,
+            honour_size_hints: false,
             state: None,
+            hooks: Hooks::default(),
+            alias: HashMap::new(),
+            alias_commands: HashMap::new(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use leftwm_core::display_servers::HeadlessDisplayServer;
+    use leftwm_core::utils::command_socket::Reply;
+    use leftwm_core::Command;
+
+    #[test]
+    fn typed_formats_should_hold_commands_with_arguments() {
+        let config = r#"(
+            modkey: "Mod1",
+            keybind: [
+                (command: MoveFloating(0, -20), modifier: ["modkey"], key: "k"),
+                (command: SetLayout(Monocle), modifier: ["modkey"], key: "m"),
+            ],
+        )"#;
+        let config = ConfigFormat::Ron.parse(config).unwrap();
+        assert_eq!(config.modkey, "Mod1");
+        assert_eq!(config.keybind[0].command, Command::MoveFloating(0, -20));
+        assert_eq!(
+            config.keybind[1].command,
+            Command::SetLayout(Layout::Monocle)
+        );
+
+        for format in [ConfigFormat::Ron, ConfigFormat::Json, ConfigFormat::Yaml] {
+            let contents = format.serialize(&config).unwrap();
+            let parsed = format.parse(&contents).unwrap();
+            assert_eq!(parsed.keybind[0].command, Command::MoveFloating(0, -20));
+        }
+    }
+
+    #[test]
+    fn toml_keybinds_should_be_converted_to_commands() {
+        let config = r#"
+modkey = "Mod1"
+
+[[keybind]]
+command = "ResizeFloating"
+value = "10 -10"
+modifier = ["modkey"]
+key = "r"
+
+[[keybind]]
+command = "GotoTag"
+value = "one"
+modifier = ["modkey"]
+key = "1"
+"#;
+        let config = ConfigFormat::Toml.parse(config).unwrap();
+        assert_eq!(config.keybind.len(), 1);
+        assert_eq!(config.keybind[0].command, Command::ResizeFloating(10, -10));
+    }
+
+    #[test]
+    fn aliases_and_sequences_should_be_read_from_toml() {
+        let config = r#"
+[alias]
+follow3 = ["SendWindowToTag 3", "GotoTag 3"]
+broken = ["GotoTag three"]
+
+[[keybind]]
+command = "Sequence"
+value = "'SendWindowToTag 3' 'GotoTag 3'"
+modifier = ["modkey"]
+key = "3"
+"#;
+        let config = ConfigFormat::Toml.parse(config).unwrap();
+        let follow3 = vec![Command::SendWindowToTag(3), Command::GotoTag(3)];
+        assert_eq!(
+            config.keybind[0].command,
+            Command::Sequence(follow3.clone())
+        );
+        let aliases = leftwm_core::Config::aliases(&config);
+        assert_eq!(aliases.get("follow3"), Some(&follow3));
+        assert!(!aliases.contains_key("broken"));
+    }
+
+    #[test]
+    fn includes_should_append_lists_and_override_values() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("config.toml"),
+            r#"
+include = ["keybinds.toml", "hosts/${LEFTWM_TEST_HOST}.toml"]
+modkey = "Mod1"
+tags = ["1", "2"]
+
+[[keybind]]
+command = "CloseWindow"
+modifier = ["modkey"]
+key = "q"
+"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("keybinds.toml"),
+            r#"
+[[keybind]]
+command = "NextLayout"
+modifier = ["modkey"]
+key = "n"
+"#,
+        )
+        .unwrap();
+        fs::create_dir(dir.path().join("hosts")).unwrap();
+        fs::write(
+            dir.path().join("hosts/laptop.toml"),
+            r#"
+modkey = "Mod4"
+tags = ["3"]
+
+[[workspaces]]
+output = "eDP-1"
+"#,
+        )
+        .unwrap();
+        env::set_var("LEFTWM_TEST_HOST", "laptop");
+
+        let config = read_toml(&dir.path().join("config.toml")).unwrap();
+        assert_eq!(config.modkey, "Mod4");
+        assert_eq!(config.tags, Some(vec!["1".into(), "2".into(), "3".into()]));
+        assert_eq!(config.keybind.len(), 2);
+        assert_eq!(
+            config.workspaces.unwrap()[0].output.as_deref(),
+            Some("eDP-1")
+        );
+    }
+
+    #[test]
+    fn unknown_commands_and_missing_themes_should_be_reported_to_clients() {
+        let mut manager = Manager::<Config, HeadlessDisplayServer>::new(Config::default());
+        let reply = |result| match Reply::from(&result) {
+            Reply::Failure { reason } => reason,
+            reply => panic!("{:?}", reply),
+        };
+        let unknown = manager.checked_command_handler(&Command::Other("Frobnicate".to_string()));
+        assert_eq!(
+            unknown,
+            Err(CommandError::Unknown("Frobnicate".to_string()))
+        );
+        assert_eq!(reply(unknown), "Unknown command `Frobnicate`");
+
+        let theme = "LoadTheme /missing/theme.toml".to_string();
+        let missing = manager.checked_command_handler(&Command::Other(theme));
+        assert_eq!(
+            reply(missing),
+            "Cannot load the theme /missing/theme.toml: it does not exist"
+        );
+    }
+}
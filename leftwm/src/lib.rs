@@ -1,5 +1,7 @@
+pub mod check;
 mod command;
 mod config;
+pub mod migration;
 mod theme_setting;
 
 pub use command::*;
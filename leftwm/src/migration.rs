@@ -0,0 +1,136 @@
+//! Migrates `config.toml` files written for older versions of `LeftWM` to the current schema.
+//!
+//! Configs are migrated in memory every time they are read, so renamed settings keep working.
+//! `leftwm-check --migrate` writes the migrated config back to disk.
+use anyhow::{bail, Result};
+use toml_edit::{Document, Item};
+
+/// The version of the config schema. Bump it along with adding a migration to `MIGRATIONS`.
+pub const CONFIG_VERSION: i64 = 1;
+
+/// A migration from one version to the next, returning a description of each change.
+type Migration = fn(&mut Document) -> Vec<String>;
+
+/// The migration at index `n` migrates a config from version `n` to version `n + 1`.
+const MIGRATIONS: [Migration; CONFIG_VERSION as usize] = [rename_commands];
+
+/// Commands of keybinds renamed to match the names of the commands in `leftwm_core`.
+const RENAMED_COMMANDS: [(&str, &str); 3] = [
+    ("SwapTags", "SwapScreens"),
+    ("MoveToTag", "SendWindowToTag"),
+    ("MoveToLastWorkspace", "MoveWindowToLastWorkspace"),
+];
+
+/// A config migrated to [`CONFIG_VERSION`].
+#[derive(Debug)]
+pub struct Migrated {
+    /// The version the config was written for, `0` if it has no `version`.
+    pub from_version: i64,
+    pub contents: String,
+    /// What was rewritten, empty if the config only needed a new `version`.
+    pub changes: Vec<String>,
+}
+
+/// Migrates the contents of a `config.toml`, keeping its formatting and comments.
+///
+/// # Errors
+///
+/// Will error if the contents are not valid TOML, or were written for a newer version of
+/// `LeftWM`.
+pub fn migrate(contents: &str) -> Result<Migrated> {
+    let mut document: Document = contents.parse()?;
+    let from_version = document
+        .get("version")
+        .and_then(Item::as_integer)
+        .unwrap_or(0);
+    if from_version > CONFIG_VERSION {
+        bail!(
+            "the config is for version {} but this LeftWM only knows version {}",
+            from_version,
+            CONFIG_VERSION
+        );
+    }
+    let changes = MIGRATIONS[from_version.max(0) as usize..]
+        .iter()
+        .flat_map(|migration| migration(&mut document))
+        .collect();
+    document["version"] = toml_edit::value(CONFIG_VERSION);
+    Ok(Migrated {
+        from_version,
+        contents: document.to_string(),
+        changes,
+    })
+}
+
+fn rename_commands(document: &mut Document) -> Vec<String> {
+    let mut changes = vec![];
+    let keybinds = match document
+        .get_mut("keybind")
+        .and_then(Item::as_array_of_tables_mut)
+    {
+        Some(keybinds) => keybinds,
+        None => return changes,
+    };
+    for (index, keybind) in keybinds.iter_mut().enumerate() {
+        let command = keybind.get("command").and_then(Item::as_str);
+        let renamed = RENAMED_COMMANDS
+            .iter()
+            .find(|(old, _)| Some(*old) == command);
+        if let Some((old, new)) = renamed {
+            let decor = keybind["command"].as_value().map(|v| v.decor().clone());
+            keybind["command"] = toml_edit::value(*new);
+            if let (Some(decor), Some(value)) = (decor, keybind["command"].as_value_mut()) {
+                *value.decor_mut() = decor;
+            }
+            changes.push(format!(
+                "keybind {}: command `{}` is now `{}`",
+                index + 1,
+                old,
+                new
+            ));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renamed_commands_should_be_migrated() {
+        let config = r#"# my config
+modkey = "Mod4"
+
+[[keybind]]
+command = "SwapTags" # swap the screens
+modifier = ["modkey"]
+key = "w"
+
+[[keybind]]
+command = "CloseWindow"
+modifier = ["modkey"]
+key = "q"
+"#;
+        let migrated = migrate(config).unwrap();
+        assert_eq!(migrated.from_version, 0);
+        assert_eq!(
+            migrated.changes,
+            vec!["keybind 1: command `SwapTags` is now `SwapScreens`"]
+        );
+        assert!(migrated.contents.starts_with("# my config\n"));
+        assert!(migrated
+            .contents
+            .contains("command = \"SwapScreens\" # swap the screens"));
+        assert!(migrated.contents.contains("version = 1"));
+
+        let again = migrate(&migrated.contents).unwrap();
+        assert_eq!(again.from_version, CONFIG_VERSION);
+        assert!(again.changes.is_empty());
+    }
+
+    #[test]
+    fn newer_configs_should_not_be_migrated() {
+        assert!(migrate("version = 1000").is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use leftwm_core::config::Hooks;
 use leftwm_core::models::{Gutter, Margins};
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -16,19 +17,21 @@ pub struct ThemeSetting {
     pub default_border_color: String,
     pub floating_border_color: String,
     pub focused_border_color: String,
+    /// Same as `hooks.on_new_window`, kept for older themes.
     #[serde(rename = "on_new_window")]
     pub on_new_window_cmd: Option<String>,
+    #[serde(default)]
+    pub hooks: Hooks,
 }
 
 impl ThemeSetting {
-    pub fn load(&mut self, path: impl AsRef<Path>) {
-        let path = path.as_ref();
-        match load_theme_file(path) {
-            Ok(theme) => *self = theme,
-            Err(err) => {
-                log::error!("Could not load theme at path {}: {}", path.display(), err);
-            }
-        }
+    /// Replaces these settings by the theme at `path`, keeping them if it cannot be loaded.
+    /// # Errors
+    ///
+    /// Will error if the theme cannot be read or is not valid.
+    pub fn load(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        *self = load_theme_file(path)?;
+        Ok(())
     }
 }
 
@@ -46,6 +49,7 @@ impl Default for ThemeSetting {
             floating_border_color: "#000000".to_owned(),
             focused_border_color: "#FF0000".to_owned(),
             on_new_window_cmd: None,
+            hooks: Hooks::default(),
         }
     }
 }
@@ -109,6 +113,9 @@ on_new_window = 'echo Hello World'
 [[gutter]]
 side = "Top"
 value = 0
+
+[hooks]
+on_focus_change = 'notify-send "$LEFTWM_WINDOW_TITLE"'
 "#;
         let config: ThemeSetting = toml::from_str(config).unwrap();
 
@@ -130,6 +137,10 @@ value = 0
                 floating_border_color: "#005500".to_string(),
                 focused_border_color: "#FFB53A".to_string(),
                 on_new_window_cmd: Some("echo Hello World".to_string()),
+                hooks: Hooks {
+                    on_focus_change: Some(r#"notify-send "$LEFTWM_WINDOW_TITLE""#.to_string()),
+                    ..Hooks::default()
+                },
             }
         );
     }